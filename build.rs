@@ -0,0 +1,96 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+/// Mirrors the runtime lookup of the inputs directory: `AOC_INPUTS`, then
+/// the `inputs` setting of `aoc.toml`, then `inputs/` next to the manifest.
+fn inputs_dir(root: &Path) -> PathBuf {
+    println!("cargo:rerun-if-env-changed=AOC_INPUTS");
+    println!("cargo:rerun-if-env-changed=AOC_CONFIG");
+    if let Some(dir) = env::var_os("AOC_INPUTS") {
+        return root.join(dir);
+    }
+    let config = env::var_os("AOC_CONFIG").map_or_else(|| root.join("aoc.toml"), PathBuf::from);
+    // watching a missing file makes cargo rebuild every time; a config
+    // created later is picked up with the next change to the inputs
+    if config.exists() {
+        println!("cargo:rerun-if-changed={}", config.display());
+    }
+    let inputs = fs::read_to_string(&config)
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|config| config.get("inputs")?.as_str().map(String::from));
+    match inputs {
+        Some(inputs) => config.parent().unwrap_or(root).join(inputs),
+        None => root.join("inputs"),
+    }
+}
+
+fn file_names(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect()
+}
+
+/// Fixture names relative to the inputs directory: `exampleNN.toml` for the
+/// default year and `YYYY/exampleNN.toml` for the others.
+fn fixture_names(inputs: &Path) -> Vec<String> {
+    let is_fixture = |name: &String| name.starts_with("example") && name.ends_with(".toml");
+    let mut names: Vec<String> = file_names(inputs).into_iter().filter(is_fixture).collect();
+    for year in file_names(inputs) {
+        if year.len() == 4 && year.chars().all(|c| c.is_ascii_digit()) {
+            let dir = inputs.join(&year);
+            println!("cargo:rerun-if-changed={}", dir.display());
+            names.extend(
+                file_names(&dir)
+                    .into_iter()
+                    .filter(is_fixture)
+                    .map(|name| format!("{}/{}", year, name)),
+            );
+        }
+    }
+    names.sort();
+    names
+}
+
+/// Generates one test per fixture and per part with an expected answer.
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let inputs = inputs_dir(&root);
+    println!("cargo:rerun-if-changed={}", inputs.display());
+
+    let mut tests = String::new();
+    for name in fixture_names(&inputs) {
+        let path = inputs.join(&name);
+        println!("cargo:rerun-if-changed={}", path.display());
+        let fixture = fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| content.parse::<toml::Value>().ok());
+        let test_name: String = match name.split_once('/') {
+            Some((year, file)) => format!("y{}_{}", year, file),
+            None => name.clone(),
+        }
+        .trim_end_matches(".toml")
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+        // `ignore = "reason"` keeps the fixture of a day that isn't solved yet
+        let ignore = fixture
+            .as_ref()
+            .and_then(|f| f.get("ignore")?.as_str())
+            .map_or_else(String::new, |reason| format!("#[ignore = {:?}]\n", reason));
+        for (key, part) in [("part1", "One"), ("part2", "Two")] {
+            if fixture.as_ref().and_then(|f| f.get(key)).is_some() {
+                tests.push_str(&format!(
+                    "#[test]\n{}fn {}_{}() {{\n    check({:?}, Part::{});\n}}\n\n",
+                    ignore, test_name, key, name, part
+                ));
+            }
+        }
+    }
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("fixture_tests.rs");
+    fs::write(out, tests).unwrap();
+}
@@ -0,0 +1,97 @@
+use std::fmt::Display;
+
+use num_bigint::{BigInt, BigUint};
+
+use crate::ocr::Grid;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Answer {
+    String(String),
+    Int(i32),
+    I64(i64),
+    U64(u64),
+    U128(u128),
+    Big(BigInt),
+    Grid(Grid),
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::String(x) => write!(f, "{}", x),
+            Answer::Int(x) => write!(f, "{}", x),
+            Answer::I64(x) => write!(f, "{}", x),
+            Answer::U64(x) => write!(f, "{}", x),
+            Answer::U128(x) => write!(f, "{}", x),
+            Answer::Big(x) => write!(f, "{}", x),
+            Answer::Grid(x) => match x.ocr() {
+                Some(text) => write!(f, "{}", text),
+                None => write!(f, "unrecognized {}×{} grid", x.width(), x.height()),
+            },
+        }
+    }
+}
+
+macro_rules! answer_from {
+    ($($from: ty => $variant: ident),* $(,)?) => {
+        $(
+            impl From<$from> for Answer {
+                fn from(x: $from) -> Self {
+                    Self::$variant(x.into())
+                }
+            }
+        )*
+    };
+}
+
+answer_from!(
+    i32 => Int,
+    i64 => I64,
+    u32 => U64,
+    u64 => U64,
+    u128 => U128,
+    BigInt => Big,
+    BigUint => Big,
+    Grid => Grid,
+);
+
+impl From<usize> for Answer {
+    fn from(x: usize) -> Self {
+        Self::U64(x as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(x: String) -> Self {
+        Self::String(x)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(x: &str) -> Self {
+        Self::String(x.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+        let big = BigUint::from(u128::MAX) * 10u8;
+        assert_eq!(Answer::from(big.clone()), Answer::Big(big.clone().into()));
+        assert_eq!(Answer::from(big).to_string(), format!("{}0", u128::MAX));
+        assert_eq!(Answer::from(42usize), Answer::U64(42));
+        assert_eq!(Answer::from(usize::MAX).to_string(), usize::MAX.to_string());
+    }
+
+    #[test]
+    fn test_display_grid() {
+        let grid = Grid::from_pixels(".##.\n#..#\n#..#\n####\n#..#\n#..#");
+        assert_eq!(Answer::from(grid).to_string(), "A");
+        let grid = Grid::from_pixels("#.#\n.#.\n#.#\n.#.\n#.#\n.#.");
+        assert_eq!(Answer::from(grid).to_string(), "unrecognized 3×6 grid");
+    }
+}
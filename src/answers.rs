@@ -0,0 +1,181 @@
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use aoc22::{config::Config, error::AocError, solvers::Part};
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// Known-good answers, stored in the year's inputs directory as
+///
+/// ```toml
+/// [day05]
+/// part1 = "CMZ"
+/// part2 = "MCD"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Answers(BTreeMap<(u8, Part), String>);
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    Missing,
+}
+
+pub fn table_name(day: u8) -> String {
+    format!("day{:02}", day)
+}
+
+pub fn key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+impl Answers {
+    pub fn path(year: u16) -> Result<PathBuf, AocError> {
+        Ok(Config::load()?.year_dir(year)?.join(ANSWERS_FILE))
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, AocError> {
+        let error = |message: String| AocError::Parse(format!("{}: {}", ANSWERS_FILE, message));
+        let table: toml::value::Table = toml::from_str(s).map_err(|e| error(e.to_string()))?;
+        let mut answers = Answers::default();
+        for (name, parts) in table {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| error(format!("[{}] is not a day", name)))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| error(format!("[{}] is not a table", name)))?;
+            for (k, answer) in parts {
+                let part = Part::ALL
+                    .into_iter()
+                    .find(|p| key(*p) == *k)
+                    .ok_or_else(|| error(format!("unknown part {} in [{}]", k, name)))?;
+                let answer = answer
+                    .as_str()
+                    .ok_or_else(|| error(format!("{}.{} must be a string", name, k)))?;
+                answers.0.insert((day, part), answer.to_string());
+            }
+        }
+        Ok(answers)
+    }
+
+    /// Loads the answers file, treating a missing file as having no answers.
+    pub fn load(year: u16) -> Result<Answers, AocError> {
+        let path = Self::path(year)?;
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(AocError::Read(path.display().to_string(), e)),
+        }
+    }
+
+    pub fn save(&self, year: u16) -> Result<(), AocError> {
+        self.write(&Self::path(year)?)
+    }
+
+    /// Writes the answers to `path`, creating its directory first.
+    fn write(&self, path: &Path) -> Result<(), AocError> {
+        let error = |e| AocError::Write(path.display().to_string(), e);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        fs::write(path, self.to_toml()).map_err(error)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = toml::value::Table::new();
+        for ((day, part), answer) in &self.0 {
+            table
+                .entry(table_name(*day))
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+                .as_table_mut()
+                .unwrap()
+                .insert(key(*part), toml::Value::String(answer.clone()));
+        }
+        toml::to_string(&table).unwrap()
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: String) {
+        self.0.insert((day, part), answer);
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Verdict {
+        match self.get(day, part) {
+            Some(expected) if expected == answer => Verdict::Pass,
+            Some(expected) => Verdict::Fail {
+                expected: expected.to_string(),
+            },
+            None => Verdict::Missing,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers =
+            Answers::parse("[day05]\npart1 = \"CMZ\"\n\n[day01]\npart2 = \"45000\"\n").unwrap();
+        assert_eq!(answers.get(5, Part::One), Some("CMZ"));
+        assert_eq!(answers.get(5, Part::Two), None);
+        assert_eq!(answers.get(1, Part::Two), Some("45000"));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("[5]\npart1 = \"CMZ\"").is_err());
+        assert!(Answers::parse("[day05]\npart3 = \"CMZ\"").is_err());
+        assert!(Answers::parse("[day05]\npart1 = 12").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut answers = Answers::default();
+        answers.insert(12, Part::Two, "x".to_string());
+        answers.insert(2, Part::One, "15".to_string());
+        let toml = answers.to_toml();
+        assert_eq!(toml, "[day02]\npart1 = \"15\"\n\n[day12]\npart2 = \"x\"\n");
+        assert_eq!(Answers::parse(&toml).unwrap(), answers);
+    }
+
+    #[test]
+    fn test_write_creates_year_dir() {
+        let inputs = std::env::temp_dir().join(format!("aoc22-answers-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&inputs);
+        let config = Config::parse(&format!("inputs = {:?}", inputs), &inputs).unwrap();
+        let path = config.year_dir(2023).unwrap().join(ANSWERS_FILE);
+        let mut answers = Answers::default();
+        answers.insert(1, Part::One, "142".to_string());
+        answers.write(&path).unwrap();
+        assert_eq!(
+            Answers::parse(&fs::read_to_string(&path).unwrap()).unwrap(),
+            answers
+        );
+        fs::remove_dir_all(inputs).unwrap();
+    }
+
+    #[test]
+    fn test_check() {
+        let mut answers = Answers::default();
+        answers.insert(5, Part::One, "CMZ".to_string());
+        assert_eq!(answers.check(5, Part::One, "CMZ"), Verdict::Pass);
+        assert_eq!(
+            answers.check(5, Part::One, "MCD"),
+            Verdict::Fail {
+                expected: "CMZ".to_string()
+            }
+        );
+        assert_eq!(answers.check(5, Part::Two, "MCD"), Verdict::Missing);
+    }
+}
@@ -0,0 +1,206 @@
+use std::{collections::HashMap, fs, io, path::Path, time::Duration};
+
+use aoc22::{
+    error::AocError,
+    solvers::{timed, Params, Part, Puzzle, DEFAULT_YEAR},
+};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Stats {
+    pub min: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    pub fn from_samples(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples to compute statistics of");
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = nanos.len();
+        let mean = nanos.iter().sum::<f64>() / n as f64;
+        let median = (nanos[(n - 1) / 2] + nanos[n / 2]) / 2.0;
+        let variance = if n > 1 {
+            nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1) as f64
+        } else {
+            0.0
+        };
+        Stats {
+            min: Duration::from_nanos(nanos[0] as u64),
+            mean: Duration::from_nanos(mean.round() as u64),
+            median: Duration::from_nanos(median.round() as u64),
+            stddev: Duration::from_nanos(variance.sqrt().round() as u64),
+        }
+    }
+}
+
+pub struct Measurement {
+    pub year: u16,
+    pub day: u8,
+    pub part: Part,
+    pub stats: Stats,
+}
+
+/// Parses the input once, then times each part `runs` times after a single
+/// unmeasured warm-up run.
+pub fn bench(puzzle: &dyn Puzzle, input: &str, runs: usize) -> Result<Vec<Measurement>, AocError> {
+    let parsed = puzzle.parse(input)?;
+    let params = Params::defaults(puzzle.params());
+    Part::ALL
+        .into_iter()
+        .map(|part| {
+            parsed.solve(part, &params)?;
+            let samples: Vec<Duration> = (0..runs)
+                .map(|_| timed(|| parsed.solve(part, &params)).1)
+                .collect();
+            Ok(Measurement {
+                year: puzzle.year(),
+                day: puzzle.day(),
+                part,
+                stats: Stats::from_samples(&samples),
+            })
+        })
+        .collect()
+}
+
+/// Mean times keyed by year, day and part, saved one `year day part nanos`
+/// line each.
+pub type Baseline = HashMap<(u16, u8, Part), Duration>;
+
+pub fn format_baseline(baseline: &Baseline) -> String {
+    let mut entries: Vec<_> = baseline.iter().collect();
+    entries.sort();
+    entries
+        .into_iter()
+        .map(|((year, day, part), mean)| format!("{} {} {} {}\n", year, day, part, mean.as_nanos()))
+        .collect()
+}
+
+/// Parses a saved baseline. Lines without a year, as written before years
+/// were recorded, belong to the default year.
+pub fn parse_baseline(s: &str) -> Option<Baseline> {
+    let mut baseline = Baseline::new();
+    for line in s.lines().filter(|l| !l.trim().is_empty()) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        let (year, fields) = match fields.len() {
+            3 => (DEFAULT_YEAR, &fields[..]),
+            4 => (fields[0].parse().ok()?, &fields[1..]),
+            _ => return None,
+        };
+        let day = fields[0].parse().ok()?;
+        let part = fields[1].parse().ok()?;
+        let mean = fields[2].parse().ok()?;
+        baseline.insert((year, day, part), Duration::from_nanos(mean));
+    }
+    Some(baseline)
+}
+
+/// Merges the measurements into the baseline stored at `path`, so benching a
+/// single day does not drop the other days' entries. A malformed existing
+/// file is an error rather than something to overwrite.
+pub fn save_baseline(path: &Path, measurements: &[Measurement]) -> Result<(), AocError> {
+    let mut baseline = match load_baseline(path) {
+        Err(AocError::Read(_, e)) if e.kind() == io::ErrorKind::NotFound => Baseline::new(),
+        baseline => baseline?,
+    };
+    for m in measurements {
+        baseline.insert((m.year, m.day, m.part), m.stats.mean);
+    }
+    fs::write(path, format_baseline(&baseline))
+        .map_err(|e| AocError::Write(path.display().to_string(), e))
+}
+
+pub fn load_baseline(path: &Path) -> Result<Baseline, AocError> {
+    let content =
+        fs::read_to_string(path).map_err(|e| AocError::Read(path.display().to_string(), e))?;
+    parse_baseline(&content)
+        .ok_or_else(|| AocError::Parse(format!("{} is not a baseline file", path.display())))
+}
+
+/// Relative change of `current` against `baseline`, e.g. `0.25` for 25% slower.
+pub fn change(current: Duration, baseline: Duration) -> f64 {
+    current.as_nanos() as f64 / baseline.as_nanos().max(1) as f64 - 1.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(x: u64) -> Duration {
+        Duration::from_millis(x)
+    }
+
+    #[test]
+    fn test_stats_odd() {
+        let stats = Stats::from_samples(&[ms(3), ms(1), ms(2)]);
+        assert_eq!(
+            stats,
+            Stats {
+                min: ms(1),
+                mean: ms(2),
+                median: ms(2),
+                stddev: ms(1),
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_even() {
+        let stats = Stats::from_samples(&[ms(4), ms(1), ms(2), ms(1)]);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.mean, ms(2));
+        assert_eq!(stats.median, Duration::from_micros(1500));
+        assert_eq!(stats.stddev, Duration::from_nanos(1_414_214));
+    }
+
+    #[test]
+    fn test_baseline_round_trip() {
+        let baseline: Baseline = [
+            ((2022, 5, Part::Two), ms(7)),
+            ((2022, 1, Part::One), ms(3)),
+            ((2021, 5, Part::Two), ms(2)),
+        ]
+        .into();
+        assert_eq!(
+            format_baseline(&baseline),
+            "2021 5 2 2000000\n2022 1 1 3000000\n2022 5 2 7000000\n"
+        );
+        assert_eq!(parse_baseline(&format_baseline(&baseline)), Some(baseline));
+        assert_eq!(parse_baseline("5 3 100"), None);
+        assert_eq!(parse_baseline("2022 5 2"), None);
+        assert_eq!(
+            parse_baseline("1 1 3000000\n"),
+            Some([((DEFAULT_YEAR, 1, Part::One), ms(3))].into())
+        );
+    }
+
+    #[test]
+    fn test_save_baseline() {
+        let path = std::env::temp_dir().join(format!("aoc22-baseline-{}", std::process::id()));
+        let _ = fs::remove_file(&path);
+        let measurement = |day, mean| Measurement {
+            year: 2022,
+            day,
+            part: Part::One,
+            stats: Stats::from_samples(&[mean]),
+        };
+        save_baseline(&path, &[measurement(1, ms(3))]).unwrap();
+        save_baseline(&path, &[measurement(2, ms(4))]).unwrap();
+        assert_eq!(load_baseline(&path).unwrap().len(), 2);
+
+        fs::write(&path, "not a baseline\n").unwrap();
+        assert!(matches!(
+            save_baseline(&path, &[measurement(1, ms(3))]),
+            Err(AocError::Parse(_))
+        ));
+        assert_eq!(fs::read_to_string(&path).unwrap(), "not a baseline\n");
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn test_change() {
+        assert!((change(ms(15), ms(10)) - 0.5).abs() < 1e-9);
+        assert!((change(ms(5), ms(10)) + 0.5).abs() < 1e-9);
+    }
+}
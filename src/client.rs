@@ -0,0 +1,219 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use aoc22::{config::Config, error::AocError, runner::input_file, solvers::Part};
+
+/// Talks to adventofcode.com, or to whatever server `url` points at, as the
+/// user owning the session cookie.
+pub struct Client {
+    agent: ureq::Agent,
+    url: String,
+    session: String,
+}
+
+impl Client {
+    pub fn new(url: &str, session: &str) -> Client {
+        Client {
+            agent: ureq::agent(),
+            url: url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Result<Client, AocError> {
+        let session = config.session.as_deref().ok_or_else(|| {
+            AocError::Config(
+                "no session token, set `session` in aoc.toml or AOC_SESSION".to_string(),
+            )
+        })?;
+        Ok(Client::new(config.url(), session))
+    }
+
+    fn day_url(&self, year: u16, day: u8) -> String {
+        format!("{}/{}/day/{}", self.url, year, day)
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+
+    pub fn input(&self, year: u16, day: u8) -> Result<String, AocError> {
+        let url = format!("{}/input", self.day_url(year, day));
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call();
+        read(&url, response, |status| match status {
+            400 => Some("the session token was rejected".to_string()),
+            404 => Some(format!("day {} is not unlocked yet", day)),
+            _ => None,
+        })
+    }
+
+    /// Posts the answer and returns the page judging it, see
+    /// [`crate::submit::parse_response`].
+    pub fn submit(&self, year: u16, day: u8, part: Part, answer: &str) -> Result<String, AocError> {
+        let url = format!("{}/answer", self.day_url(year, day));
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &level), ("answer", answer)]);
+        read(&url, response, |status| match status {
+            400 => Some("the session token was rejected".to_string()),
+            404 => Some(format!("day {} is not unlocked yet", day)),
+            _ => None,
+        })
+    }
+}
+
+/// Reads the response body, explaining error statuses with `describe` where
+/// it knows better than the bare status line.
+fn read(
+    url: &str,
+    response: Result<ureq::Response, ureq::Error>,
+    describe: impl Fn(u16) -> Option<String>,
+) -> Result<String, AocError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| AocError::Read(url.to_string(), e)),
+        Err(ureq::Error::Status(code, response)) => {
+            let status = format!("{} answered {} {}", url, code, response.status_text());
+            Err(AocError::Http(match describe(code) {
+                Some(reason) => format!("{} ({})", reason, status),
+                None => status,
+            }))
+        }
+        Err(e) => Err(AocError::Http(e.to_string())),
+    }
+}
+
+/// Returns the path of the day's input, downloading it first unless it is
+/// already cached in `inputs`, the directory of the year. The flag tells
+/// whether it was downloaded.
+pub fn fetch_input(
+    client: &Client,
+    inputs: &Path,
+    year: u16,
+    day: u8,
+) -> Result<(PathBuf, bool), AocError> {
+    let path = inputs.join(input_file(day));
+    // `new` leaves an empty placeholder behind, which doesn't count as cached
+    if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+        return Ok((path, false));
+    }
+    let input = client.input(year, day)?;
+    fs::create_dir_all(inputs).map_err(|e| AocError::Write(inputs.display().to_string(), e))?;
+    fs::write(&path, input).map_err(|e| AocError::Write(path.display().to_string(), e))?;
+    Ok((path, true))
+}
+
+#[cfg(test)]
+pub mod mock {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    /// Serves the canned `(status, body)` responses in order, one per
+    /// connection, and returns the raw requests it received once done.
+    pub fn serve(responses: Vec<(u16, &'static str)>) -> (String, JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for (status, body) in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some((name, value)) = line.split_once(':') {
+                        if name.eq_ignore_ascii_case("content-length") {
+                            length = value.trim().parse().unwrap();
+                        }
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" || line.is_empty() {
+                        break;
+                    }
+                }
+                let mut body_bytes = vec![0; length];
+                reader.read_exact(&mut body_bytes).unwrap();
+                request.push_str(&String::from_utf8(body_bytes).unwrap());
+                requests.push(request);
+                write!(
+                    stream,
+                    "HTTP/1.1 {} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                )
+                .unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, process};
+
+    use super::*;
+
+    fn scratch(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc22-{}-{}", name, process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_fetch_input_is_cached() {
+        let (url, server) = mock::serve(vec![(200, "1000\n2000\n")]);
+        let client = Client::new(&url, "cafe");
+        let inputs = scratch("fetch");
+
+        let (path, downloaded) = fetch_input(&client, &inputs, 2022, 1).unwrap();
+        assert!(downloaded);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1000\n2000\n");
+        // the server is gone after one request, so this must hit the cache
+        let requests = server.join().unwrap();
+        assert_eq!(
+            fetch_input(&client, &inputs, 2022, 1).unwrap(),
+            (path, false)
+        );
+
+        assert!(requests[0].starts_with("GET /2022/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=cafe\r\n"));
+        fs::remove_dir_all(inputs).unwrap();
+    }
+
+    #[test]
+    fn test_fetch_input_errors() {
+        let (url, server) = mock::serve(vec![(404, "not yet"), (400, "log in")]);
+        let client = Client::new(&url, "cafe");
+        let inputs = scratch("fetch-errors");
+
+        let not_unlocked = fetch_input(&client, &inputs, 2021, 25).unwrap_err();
+        assert!(not_unlocked.to_string().contains("not unlocked"));
+        let rejected = fetch_input(&client, &inputs, 2021, 25).unwrap_err();
+        assert!(rejected.to_string().contains("session token was rejected"));
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2021/day/25/input HTTP/1.1\r\n"));
+        assert!(!inputs.join(input_file(25)).exists());
+    }
+
+    #[test]
+    fn test_from_config_needs_session() {
+        assert!(matches!(
+            Client::from_config(&Config::default()),
+            Err(AocError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,168 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+use crate::{error::AocError, solvers::DEFAULT_YEAR};
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+pub const SESSION_ENV: &str = "AOC_SESSION";
+pub const URL_ENV: &str = "AOC_URL";
+pub const DEFAULT_URL: &str = "https://adventofcode.com";
+
+/// Settings read from `aoc.toml` in the working directory (or the file named
+/// by `AOC_CONFIG`), overridden by environment variables.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    pub inputs: Option<PathBuf>,
+    /// The source tree that `new`, `watch` and `readme` work in.
+    pub root: Option<PathBuf>,
+    /// Value of the `session` cookie of a logged in adventofcode.com user.
+    pub session: Option<String>,
+    pub url: Option<String>,
+}
+
+impl Config {
+    /// Parses the config file content. Relative paths are resolved against
+    /// `base`, the directory holding the file.
+    pub fn parse(s: &str, base: &Path) -> Result<Config, AocError> {
+        let table: toml::value::Table =
+            toml::from_str(s).map_err(|e| AocError::Config(e.to_string()))?;
+        let string = |key: &str| -> Result<Option<String>, AocError> {
+            match table.get(key) {
+                None => Ok(None),
+                Some(toml::Value::String(s)) => Ok(Some(s.clone())),
+                Some(_) => Err(AocError::Config(format!("{} must be a string", key))),
+            }
+        };
+        Ok(Config {
+            inputs: string("inputs")?.map(|p| base.join(p)),
+            root: string("root")?.map(|p| base.join(p)),
+            session: string("session")?,
+            url: string("url")?,
+        })
+    }
+
+    pub fn load() -> Result<Config, AocError> {
+        let cwd = env::current_dir()?;
+        let file = env::var_os(CONFIG_ENV).map_or_else(|| cwd.join(CONFIG_FILE), PathBuf::from);
+        let mut config = match fs::read_to_string(&file) {
+            Ok(content) => Config::parse(&content, file.parent().unwrap_or(&cwd))?,
+            Err(_) if env::var_os(CONFIG_ENV).is_none() => Config::default(),
+            Err(e) => return Err(AocError::Read(file.display().to_string(), e)),
+        };
+        if let Some(inputs) = env::var_os(INPUTS_ENV) {
+            config.inputs = Some(cwd.join(inputs));
+        }
+        if let Ok(session) = env::var(SESSION_ENV) {
+            config.session = Some(session);
+        }
+        if let Ok(url) = env::var(URL_ENV) {
+            config.url = Some(url);
+        }
+        Ok(config)
+    }
+
+    pub fn inputs_dir(&self) -> Result<PathBuf, AocError> {
+        match &self.inputs {
+            Some(inputs) => Ok(inputs.clone()),
+            None => Ok(env::current_dir()?.join("inputs")),
+        }
+    }
+
+    /// Where the year's inputs, examples and answers live: the inputs
+    /// directory itself for the default year, else its `<year>` subdirectory.
+    pub fn year_dir(&self, year: u16) -> Result<PathBuf, AocError> {
+        let inputs = self.inputs_dir()?;
+        Ok(if year == DEFAULT_YEAR {
+            inputs
+        } else {
+            inputs.join(year.to_string())
+        })
+    }
+
+    /// The `root` setting, else the nearest directory from the working
+    /// directory up that holds a `Cargo.toml`.
+    pub fn root(&self) -> Result<PathBuf, AocError> {
+        if let Some(root) = &self.root {
+            return Ok(root.clone());
+        }
+        let cwd = env::current_dir()?;
+        find_root(&cwd).ok_or_else(|| {
+            AocError::Config(format!(
+                "no Cargo.toml in {} or above it, set root in {}",
+                cwd.display(),
+                CONFIG_FILE
+            ))
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        self.url.as_deref().unwrap_or(DEFAULT_URL)
+    }
+}
+
+fn find_root(dir: &Path) -> Option<PathBuf> {
+    dir.ancestors()
+        .find(|d| d.join("Cargo.toml").is_file())
+        .map(Path::to_path_buf)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse("inputs = \"puzzles\"\n", Path::new("/home/elf")).unwrap();
+        assert_eq!(config.inputs, Some(PathBuf::from("/home/elf/puzzles")));
+
+        let config = Config::parse("inputs = \"/srv/aoc\"", Path::new("/home/elf")).unwrap();
+        assert_eq!(config.inputs, Some(PathBuf::from("/srv/aoc")));
+        assert_eq!(config.url(), DEFAULT_URL);
+        assert_eq!(config.year_dir(2022).unwrap(), PathBuf::from("/srv/aoc"));
+        assert_eq!(
+            config.year_dir(2023).unwrap(),
+            PathBuf::from("/srv/aoc/2023")
+        );
+
+        let config = Config::parse(
+            "session = \"53616c74\"\nurl = \"http://localhost:8080\"",
+            Path::new("."),
+        )
+        .unwrap();
+        assert_eq!(config.session.as_deref(), Some("53616c74"));
+        assert_eq!(config.url(), "http://localhost:8080");
+
+        assert_eq!(
+            Config::parse("", Path::new(".")).unwrap(),
+            Config::default()
+        );
+    }
+
+    #[test]
+    fn test_root() {
+        let config = Config::parse("root = \"aoc\"", Path::new("/home/elf")).unwrap();
+        assert_eq!(config.root().unwrap(), PathBuf::from("/home/elf/aoc"));
+        let manifest = Path::new(env!("CARGO_MANIFEST_DIR"));
+        assert_eq!(find_root(&manifest.join("src/solvers")).unwrap(), manifest);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(
+            Config::parse("inputs = 5", Path::new(".")),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            Config::parse("inputs = ", Path::new(".")),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            Config::parse("session = 1", Path::new(".")),
+            Err(AocError::Config(_))
+        ));
+    }
+}
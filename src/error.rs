@@ -0,0 +1,185 @@
+use std::{fmt::Display, io, ops::Range};
+
+use crate::solvers::{day04::SectionParseError, day05, day07};
+
+/// Where in the input a parse error happened: 1-based line number, byte
+/// range of the offending part of the line and the line itself.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Location {
+    pub line: usize,
+    pub columns: Range<usize>,
+    pub text: String,
+}
+
+impl Location {
+    pub fn new(line: usize, columns: Range<usize>, text: &str) -> Self {
+        Self {
+            line,
+            columns,
+            text: text.to_string(),
+        }
+    }
+
+    /// Location spanning the whole line.
+    pub fn line(line: usize, text: &str) -> Self {
+        Self::new(line, 0..text.len(), text)
+    }
+
+    /// Location of `part`, which must be a subslice of `text`.
+    pub fn within(line: usize, text: &str, part: &str) -> Self {
+        let start = (part.as_ptr() as usize).saturating_sub(text.as_ptr() as usize);
+        Self::new(line, start..start + part.len(), text)
+    }
+
+    fn column(&self, offset: usize) -> usize {
+        self.text
+            .get(..offset)
+            .map_or(offset, |s| s.chars().count())
+    }
+
+    /// Renders the line with a caret underline below the offending columns.
+    pub fn render(&self) -> String {
+        let gutter = " ".repeat(self.line.to_string().len());
+        let start = self.column(self.columns.start);
+        let width = self.column(self.columns.end).saturating_sub(start).max(1);
+        format!(
+            "{gutter}--> line {}, column {}\n{gutter} |\n{} | {}\n{gutter} | {}{}",
+            self.line,
+            start + 1,
+            self.line,
+            self.text,
+            " ".repeat(start),
+            "^".repeat(width),
+            gutter = gutter
+        )
+    }
+}
+
+#[derive(Debug)]
+pub enum AocError {
+    Io(io::Error),
+    Read(String, io::Error),
+    Write(String, io::Error),
+    UnknownDay(u16, u8),
+    Config(String),
+    Argument(String),
+    Param(String),
+    Http(String),
+    Parse(String),
+    Solve(String),
+    Panic(String),
+    Section(SectionParseError),
+    SupplyStacks(day05::ParseError),
+    Transcript(day07::ParsingError),
+    At(Location, Box<AocError>),
+}
+
+impl AocError {
+    pub fn at(self, location: Location) -> Self {
+        Self::At(location, Box::new(self))
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocError::Io(e) => write!(f, "{}", e),
+            AocError::Read(file, e) => write!(f, "cannot read {}: {}", file, e),
+            AocError::Write(file, e) => write!(f, "cannot write {}: {}", file, e),
+            AocError::UnknownDay(year, day) => {
+                write!(f, "day {} of {} is not solved yet", day, year)
+            }
+            AocError::Config(message) => write!(f, "invalid configuration: {}", message),
+            AocError::Argument(message) => write!(f, "invalid argument: {}", message),
+            AocError::Param(message) => write!(f, "invalid parameter: {}", message),
+            AocError::Http(message) => write!(f, "request failed: {}", message),
+            AocError::Parse(message) => write!(f, "malformed input: {}", message),
+            AocError::Solve(message) => write!(f, "cannot solve: {}", message),
+            AocError::Panic(message) => write!(f, "solver panicked: {}", message),
+            AocError::Section(e) => write!(f, "malformed section assignment: {}", e),
+            AocError::SupplyStacks(e) => write!(f, "malformed supply stacks: {}", e),
+            AocError::Transcript(e) => write!(f, "malformed terminal output: {}", e),
+            AocError::At(location, e) => write!(f, "{}\n{}", e, location.render()),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+impl From<io::Error> for AocError {
+    fn from(x: io::Error) -> Self {
+        Self::Io(x)
+    }
+}
+
+impl From<SectionParseError> for AocError {
+    fn from(x: SectionParseError) -> Self {
+        Self::Section(x)
+    }
+}
+
+impl From<day05::ParseError> for AocError {
+    fn from(x: day05::ParseError) -> Self {
+        Self::SupplyStacks(x)
+    }
+}
+
+impl From<day07::ParsingError> for AocError {
+    fn from(x: day07::ParsingError) -> Self {
+        Self::Transcript(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let text = "move 1 form 2 to 3";
+        let error = AocError::Parse("expected \"from\"".to_string()).at(Location::within(
+            12,
+            text,
+            &text[7..11],
+        ));
+        assert_eq!(
+            error.to_string(),
+            "\
+malformed input: expected \"from\"
+  --> line 12, column 8
+   |
+12 | move 1 form 2 to 3
+   |        ^^^^"
+        );
+    }
+
+    #[test]
+    fn test_render_empty_span() {
+        let location = Location::new(3, 5..5, "12-3");
+        assert_eq!(
+            location.render(),
+            " --> line 3, column 6\n  |\n3 | 12-3\n  |      ^"
+        );
+    }
+
+    #[test]
+    fn test_display_parse_errors() {
+        let size = day07::ParsingError::Size {
+            name: "b".to_string(),
+            listed: 10,
+            now: 12,
+        };
+        assert_eq!(
+            AocError::from(size).to_string(),
+            "malformed terminal output: b listed with size 12, earlier 10"
+        );
+        assert_eq!(
+            AocError::from(SectionParseError::Bound(0..1)).to_string(),
+            "malformed section assignment: expected a section number"
+        );
+        assert_eq!(
+            AocError::from(day05::ParseError::Line).to_string(),
+            "malformed supply stacks: expected a blank line after the stack numbers"
+        );
+    }
+}
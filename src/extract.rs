@@ -0,0 +1,267 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use regex::Regex;
+
+use aoc22::{error::AocError, fixtures::Fixture, runner::example_file, solvers::Part};
+
+fn unescape(html: &str) -> String {
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    tags.replace_all(html, "")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Returns `input` ending with a newline, like the example files do.
+fn with_newline(mut input: String) -> String {
+    if !input.ends_with('\n') {
+        input.push('\n');
+    }
+    input
+}
+
+/// Index of the fixture for `input`, added without answers if it is new.
+fn fixture_for(fixtures: &mut Vec<Fixture>, year: u16, day: u8, input: String) -> usize {
+    match fixtures.iter().position(|f| f.input == input) {
+        Some(i) => i,
+        None => {
+            fixtures.push(Fixture {
+                year,
+                day,
+                input,
+                expected: BTreeMap::new(),
+            });
+            fixtures.len() - 1
+        }
+    }
+}
+
+/// Pulls the examples out of a saved puzzle page. Each part's description
+/// is an `<article>`; its first `<pre><code>` block is the example and the
+/// first emphasized `<code>` after it is, by the site's convention, the
+/// answer for it. A part two without an example of its own answers the
+/// previous one with its last emphasized `<code>`. List items like
+/// `<li><code>input</code>: … <code><em>N</em></code></li>` are examples
+/// too, merged with the earlier ones that have the same input.
+pub fn extract(year: u16, day: u8, html: &str) -> Result<Vec<Fixture>, AocError> {
+    let articles = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let example = Regex::new(r"(?s)<pre><code>(.*?)</code></pre>").unwrap();
+    let item = Regex::new(r"(?s)<li><code>([^<]*)</code>:(.*?)</li>").unwrap();
+    let answer =
+        Regex::new(r"(?s)<code><em>(.*?)</em></code>|<em><code>(.*?)</code></em>").unwrap();
+    let answers = |html: &str| -> Vec<String> {
+        answer
+            .captures_iter(html)
+            .filter_map(|c| c.get(1).or_else(|| c.get(2)))
+            .map(|m| unescape(m.as_str()))
+            .collect()
+    };
+
+    let mut fixtures: Vec<Fixture> = Vec::new();
+    // the fixture of the last `<pre>` example, which a part two may reuse
+    let mut block = None;
+    for (article, part) in articles.captures_iter(html).zip(Part::ALL) {
+        let article = &article[1];
+        let items: Vec<(String, Option<String>)> = item
+            .captures_iter(article)
+            .map(|c| (unescape(&c[1]), answers(&c[2]).into_iter().next()))
+            .collect();
+        let text = item.replace_all(article, "");
+        match example.captures(&text) {
+            Some(c) => {
+                let input = with_newline(unescape(&c[1]));
+                let index = fixture_for(&mut fixtures, year, day, input);
+                let after = &text[c.get(0).unwrap().end()..];
+                if let Some(expected) = answers(after).into_iter().next() {
+                    fixtures[index].expected.insert(part, expected);
+                }
+                block = Some(index);
+            }
+            None => {
+                if let (Some(index), Some(expected)) = (block, answers(&text).pop()) {
+                    fixtures[index].expected.insert(part, expected);
+                }
+            }
+        }
+        for (input, expected) in items {
+            if let Some(expected) = expected {
+                let index = fixture_for(&mut fixtures, year, day, with_newline(input));
+                fixtures[index].expected.insert(part, expected);
+            }
+        }
+    }
+    if fixtures.is_empty() {
+        return Err(AocError::Parse(
+            "the page has no <pre><code> example blocks or example list items".to_string(),
+        ));
+    }
+    Ok(fixtures)
+}
+
+fn fixture_names(day: u8, count: usize) -> Vec<String> {
+    match count {
+        1 => vec![example_file(day)],
+        _ => (1..=count)
+            .map(|i| format!("example{:02}-{}.toml", day, i))
+            .collect(),
+    }
+}
+
+/// Writes the fixtures into `inputs`, the directory of their year. An existing fixture is only replaced
+/// with `force`, unless it is unchanged anyway. Every file is checked before
+/// any is written, so a conflict leaves all of them alone.
+pub fn write_fixtures(
+    inputs: &Path,
+    fixtures: &[Fixture],
+    force: bool,
+) -> Result<Vec<PathBuf>, AocError> {
+    let names = match fixtures.first() {
+        Some(fixture) => fixture_names(fixture.day, fixtures.len()),
+        None => return Ok(Vec::new()),
+    };
+    let mut changed = Vec::new();
+    for (name, fixture) in names.iter().zip(fixtures) {
+        let path = inputs.join(name);
+        if let Ok(existing) = fs::read_to_string(&path) {
+            if Fixture::parse(name, &existing).ok().as_ref() == Some(fixture) {
+                continue;
+            }
+            if !force {
+                return Err(AocError::Config(format!(
+                    "{} already exists and differs, use --force to replace it",
+                    path.display()
+                )));
+            }
+        }
+        changed.push((path, fixture));
+    }
+    let mut written = Vec::new();
+    for (path, fixture) in changed {
+        fs::write(&path, fixture.to_toml())
+            .map_err(|e| AocError::Write(path.display().to_string(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 6: Tuning Trouble ---</h2>
+<p>For example, suppose you receive the following datastream buffer:</p>
+<pre><code>mjqjpqmgbljsphdztnvjfqwrcgsmlb</code></pre>
+<p>After the first three characters (<code>mjq</code>) have been received, there haven't been enough characters received yet to find the marker. The first time a marker could occur is after the fourth character is received, making the most recent four characters <code>mjqj</code>. Because <code>j</code> is repeated, this isn't a marker.</p>
+<p>The first time a marker appears is after the <em>seventh</em> character arrives. Once it does, the last four characters received are <code>jpqm</code>, which are all different. In this case, your subroutine should report the value <code><em>7</em></code>, because the first start-of-packet marker is complete after 7 characters have been processed.</p>
+<p>Here are a few more examples:</p>
+<ul>
+<li><code>bvwbjplbgvbhsrlpgdmjqwftvncz</code>: first marker after character <code><em>5</em></code></li>
+<li><code>nppdvjthqldpwncqszvftbrmjlhg</code>: first marker after character <code><em>6</em></code></li>
+<li><code>nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg</code>: first marker after character <code><em>10</em></code></li>
+<li><code>zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw</code>: first marker after character <code><em>11</em></code></li>
+</ul>
+<p>How many characters need to be processed before the first start-of-packet marker is detected?</p>
+</article>
+<p>Your puzzle answer was <code>1080</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Your device's communication system is correctly detecting packets, but still isn't working. It looks like it also needs to look for <em>messages</em>.</p>
+<p>A <em>start-of-message marker</em> is just like a start-of-packet marker, except it consists of <em>14</em> distinct characters rather than 4.</p>
+<p>Here are the first positions of start-of-message markers for all of the above examples:</p>
+<ul>
+<li><code>mjqjpqmgbljsphdztnvjfqwrcgsmlb</code>: first marker after character <code><em>19</em></code></li>
+<li><code>bvwbjplbgvbhsrlpgdmjqwftvncz</code>: first marker after character <code><em>23</em></code></li>
+<li><code>nppdvjthqldpwncqszvftbrmjlhg</code>: first marker after character <code><em>23</em></code></li>
+<li><code>nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg</code>: first marker after character <code><em>29</em></code></li>
+<li><code>zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw</code>: first marker after character <code><em>26</em></code></li>
+</ul>
+<p>How many characters need to be processed before the first start-of-message marker is detected?</p>
+</article>
+</main>"#;
+
+    #[test]
+    fn test_extract() {
+        let fixtures = extract(2022, 6, PAGE).unwrap();
+        let names = fixture_names(6, fixtures.len());
+        assert_eq!(names.len(), 5);
+        for (name, fixture) in names.iter().zip(&fixtures) {
+            let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("inputs")
+                .join(name);
+            let saved = Fixture::parse(name, &fs::read_to_string(path).unwrap()).unwrap();
+            assert_eq!(fixture, &saved);
+        }
+        assert_eq!(fixtures[0].expected.get(&Part::One).unwrap(), "7");
+        assert_eq!(fixtures[4].expected.get(&Part::Two).unwrap(), "26");
+    }
+
+    #[test]
+    fn test_extract_reused_example() {
+        let page = "<article><pre><code>a\n</code></pre><code><em>1</em></code> then \
+            <code><em>2</em></code></article><article><code><em>10</em></code> and \
+            finally <em><code>3</code></em></article>";
+        let fixtures = extract(2022, 7, page).unwrap();
+        assert_eq!(fixtures.len(), 1);
+        assert_eq!(
+            fixtures[0].expected,
+            [(Part::One, "1".to_string()), (Part::Two, "3".to_string())].into()
+        );
+    }
+
+    #[test]
+    fn test_extract_own_part_two_example() {
+        let page = "<article><pre><code>[A] &lt;B&gt;\n</code></pre><code><em>A</em></code>\
+            </article><article><pre><code>x &amp; y\n</code></pre><em>no answer</em></article>";
+        let fixtures = extract(2022, 5, page).unwrap();
+        assert_eq!(fixtures.len(), 2);
+        assert_eq!(fixtures[0].input, "[A] <B>\n");
+        assert_eq!(fixtures[0].expected.get(&Part::One).unwrap(), "A");
+        assert_eq!(fixtures[1].input, "x & y\n");
+        assert!(fixtures[1].expected.is_empty());
+        assert_eq!(
+            fixture_names(5, 2),
+            vec!["example05-1.toml", "example05-2.toml"]
+        );
+    }
+
+    #[test]
+    fn test_extract_limits() {
+        // a block with attributes isn't recognized as an example
+        let page = "<article><pre class=\"x\"><code>a\n</code></pre>\
+            <code><em>1</em></code></article>";
+        assert!(extract(2022, 8, page).is_err());
+        // an example without an emphasized code keeps no answer
+        let page = "<article><pre><code>a\n</code></pre><p><em>1</em> and <code>2</code></p>\
+            </article>";
+        let fixtures = extract(2022, 8, page).unwrap();
+        assert_eq!(fixtures[0].input, "a\n");
+        assert!(fixtures[0].expected.is_empty());
+    }
+
+    #[test]
+    fn test_write_fixtures_conflict() {
+        let inputs = std::env::temp_dir().join(format!("aoc22-extract-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&inputs);
+        fs::create_dir_all(&inputs).unwrap();
+        let fixtures = extract(2022, 6, PAGE).unwrap();
+        fs::write(inputs.join("example06-3.toml"), "input = 'other'\n").unwrap();
+        assert!(write_fixtures(&inputs, &fixtures, false).is_err());
+        assert!(!inputs.join("example06-1.toml").exists());
+        assert_eq!(write_fixtures(&inputs, &fixtures, true).unwrap().len(), 5);
+        assert!(write_fixtures(&inputs, &fixtures, false)
+            .unwrap()
+            .is_empty());
+        fs::remove_dir_all(inputs).unwrap();
+    }
+
+    #[test]
+    fn test_extract_no_examples() {
+        assert!(extract(2022, 1, "<article><p>nothing</p></article>").is_err());
+    }
+}
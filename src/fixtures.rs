@@ -0,0 +1,180 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    error::AocError,
+    solvers::{Part, DEFAULT_YEAR},
+};
+
+/// A puzzle example with its expected answers, stored in the inputs directory
+/// as `exampleNN.toml` (or `exampleNN-K.toml` when a day has several):
+///
+/// ```toml
+/// part1 = "24000"
+/// part2 = "45000"
+/// input = '''
+/// 1000
+/// ...
+/// '''
+/// ```
+///
+/// A fixture of a day that isn't solved yet can carry `ignore = "reason"`
+/// to keep its generated tests ignored.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Fixture {
+    pub year: u16,
+    pub day: u8,
+    pub input: String,
+    pub expected: BTreeMap<Part, String>,
+}
+
+fn key(part: Part) -> String {
+    format!("part{}", part)
+}
+
+/// `s` as a TOML basic string.
+fn basic_string(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04X}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+impl Fixture {
+    pub fn parse(name: &str, s: &str) -> Result<Fixture, AocError> {
+        let error = |message: String| AocError::Parse(format!("{}: {}", name, message));
+        let (year, file) = match name.split_once('/') {
+            Some((year, file)) => (year.parse().ok(), file),
+            None => (Some(DEFAULT_YEAR), name),
+        };
+        let year = year.ok_or_else(|| error("expected a year directory".to_string()))?;
+        let day = file
+            .strip_prefix("example")
+            .and_then(|n| n.get(..2))
+            .and_then(|n| n.parse().ok())
+            .ok_or_else(|| error("expected a name like example05.toml".to_string()))?;
+        let table: toml::value::Table = toml::from_str(s).map_err(|e| error(e.to_string()))?;
+        let input = table
+            .get("input")
+            .and_then(toml::Value::as_str)
+            .ok_or_else(|| error("input must be a string".to_string()))?
+            .to_string();
+        let mut expected = BTreeMap::new();
+        for part in Part::ALL {
+            match table.get(&key(part)) {
+                Some(toml::Value::String(answer)) => {
+                    expected.insert(part, answer.clone());
+                }
+                Some(_) => return Err(error(format!("{} must be a string", key(part)))),
+                None => {}
+            }
+        }
+        Ok(Fixture {
+            year,
+            day,
+            input,
+            expected,
+        })
+    }
+
+    #[cfg(test)]
+    pub fn load(name: &str) -> Result<Fixture, AocError> {
+        Self::parse(name, &crate::solvers::read_lines(name)?)
+    }
+
+    /// The fixture file. The input is written as a multi-line literal string
+    /// unless it cannot be one, then as an escaped basic string.
+    pub fn to_toml(&self) -> String {
+        let mut toml = String::new();
+        for (part, answer) in &self.expected {
+            toml.push_str(&format!("{} = {}\n", key(*part), basic_string(answer)));
+        }
+        let literal = !self.input.contains("'''")
+            && !self
+                .input
+                .chars()
+                .any(|c| c.is_control() && c != '\n' && c != '\t');
+        if literal {
+            toml.push_str(&format!("input = '''\n{}'''\n", self.input));
+        } else {
+            toml.push_str(&format!("input = {}\n", basic_string(&self.input)));
+        }
+        toml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::{self, Params};
+
+    /// Runs the fixture's day on its input and compares against the expected
+    /// answer. Called by the tests generated from the fixtures by `build.rs`.
+    fn check(name: &str, part: Part) {
+        let fixture = Fixture::load(name).unwrap();
+        let puzzle = solvers::find(fixture.year, fixture.day).unwrap();
+        let parsed = puzzle.parse(&fixture.input).unwrap();
+        let params = Params::defaults(puzzle.params());
+        let answer = parsed.solve(part, &params).unwrap();
+        assert_eq!(Some(&answer.to_string()), fixture.expected.get(&part));
+    }
+
+    include!(concat!(env!("OUT_DIR"), "/fixture_tests.rs"));
+
+    #[test]
+    fn test_parse() {
+        let fixture = Fixture::parse(
+            "example06-2.toml",
+            "part1 = \"5\"\ninput = '''\nbvwbjplbgvbhsrlpgdmjqwftvncz\n'''\n",
+        )
+        .unwrap();
+        assert_eq!(
+            fixture,
+            Fixture {
+                year: 2022,
+                day: 6,
+                input: "bvwbjplbgvbhsrlpgdmjqwftvncz\n".to_string(),
+                expected: [(Part::One, "5".to_string())].into(),
+            }
+        );
+        assert_eq!(
+            Fixture::parse("example06-2.toml", &fixture.to_toml()).unwrap(),
+            fixture
+        );
+    }
+
+    #[test]
+    fn test_to_toml_escapes() {
+        let fixture = Fixture {
+            year: 2022,
+            day: 1,
+            input: "a '''quoted''' \\ \"line\"\r\n\tb\n".to_string(),
+            expected: [(Part::Two, "\"x\"".to_string())].into(),
+        };
+        let toml = fixture.to_toml();
+        assert!(toml.contains("\ninput = \"a '''quoted''' \\\\"));
+        assert_eq!(Fixture::parse("example01.toml", &toml).unwrap(), fixture);
+    }
+
+    #[test]
+    fn test_parse_year() {
+        let fixture = Fixture::parse("2023/example01-2.toml", "input = ''").unwrap();
+        assert_eq!((fixture.year, fixture.day), (2023, 1));
+        assert!(Fixture::parse("next/example01.toml", "input = ''").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Fixture::parse("sample06.toml", "input = ''").is_err());
+        assert!(Fixture::parse("example06.toml", "part1 = 5\ninput = ''").is_err());
+        assert!(Fixture::parse("example06.toml", "part1 = '5'").is_err());
+    }
+}
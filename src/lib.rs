@@ -0,0 +1,37 @@
+//! Advent of Code 2022 solutions. Every day is registered in
+//! [`solvers::SOLVERS`]; [`solve`] runs one part of a day on some input.
+
+mod answer;
+pub mod config;
+pub mod error;
+pub mod fixtures;
+pub mod ocr;
+pub mod runner;
+pub mod solvers;
+
+pub use answer::Answer;
+pub use error::AocError;
+pub use solvers::{Part, DEFAULT_YEAR, SOLVERS};
+
+/// Solves one part of a day with the solver's default parameters.
+pub fn solve(year: u16, day: u8, part: Part, input: &str) -> Result<Answer, AocError> {
+    let puzzle = solvers::find(year, day)?;
+    let params = solvers::Params::defaults(puzzle.params());
+    puzzle.parse(input)?.solve(part, &params)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve() {
+        let input = "1000\n2000\n\n3000\n";
+        assert_eq!(solve(2022, 1, Part::One, input).unwrap(), Answer::Int(3000));
+        assert_eq!(solve(2022, 1, Part::Two, input).unwrap(), Answer::Int(6000));
+        assert!(matches!(
+            solve(2022, 30, Part::One, input),
+            Err(AocError::UnknownDay(2022, 30))
+        ));
+    }
+}
@@ -1,64 +1,772 @@
-use solvers::read_lines;
-use std::{env, fmt::Display};
+use answers::{Answers, Verdict};
+use aoc22::{
+    config,
+    error::AocError,
+    runner::{self, read_input, InputSource, Run},
+    solvers::{
+        self,
+        day07::{generate, shell::Shell, Filesystem},
+        Params, Part,
+    },
+    Answer,
+};
+use report::{Format, Record};
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
 
-mod solvers;
+mod answers;
+mod bench;
+mod client;
+mod extract;
+mod report;
+mod scaffold;
+mod submit;
+mod watch;
 
+fn print_result(result: Answer, elapsed: Duration) {
+    println!("-----------------------------");
+    if let Answer::Grid(grid) = &result {
+        println!("{}", grid.render());
+    }
+    println!("Resunt is: {}", result);
+    println!("Solved in {:?}", elapsed);
+    println!("-----------------------------")
+}
 
-enum Result {
-    String(String),
-    Int(i32),
+fn print_table(rows: &[Vec<String>], left_aligned: &[usize]) {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|c| {
+            rows.iter()
+                .filter_map(|r| r.get(c))
+                .map(String::len)
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .enumerate()
+            .map(|(c, cell)| {
+                if left_aligned.contains(&c) {
+                    format!("{:<w$}", cell, w = widths[c])
+                } else {
+                    format!("{:>w$}", cell, w = widths[c])
+                }
+            })
+            .collect();
+        println!("{}", cells.join(" | ").trim_end());
+    }
 }
 
-impl Display for Result {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Result::String(x) => write!(f, "{}", x),
-            Result::Int(x) => write!(f, "{}", x),
+fn print_summary(runs: &[(u8, &str, Result<Run, AocError>)]) {
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part".to_string(),
+        "Answer".to_string(),
+        "Time".to_string(),
+    ]];
+    for (day, _, run) in runs {
+        match run {
+            Ok(run) => {
+                rows.push(vec![
+                    day.to_string(),
+                    "parse".to_string(),
+                    String::new(),
+                    format!("{:?}", run.parse_time),
+                ]);
+                for solution in &run.solutions {
+                    rows.push(vec![
+                        day.to_string(),
+                        solution.part.to_string(),
+                        match &solution.answer {
+                            Ok(answer) => answer.to_string(),
+                            Err(e) => format!("FAILED: {}", e),
+                        },
+                        format!("{:?}", solution.elapsed),
+                    ]);
+                }
+            }
+            Err(e) => rows.push(vec![
+                day.to_string(),
+                "-".to_string(),
+                format!("FAILED: {}", e),
+            ]),
         }
     }
+    print_table(&rows, &[2]);
 }
 
-impl From<i32> for Result {
-    fn from(x: i32) -> Self {
-        Self::Int(x)
+fn failed(run: &Result<Run, AocError>) -> bool {
+    match run {
+        Ok(run) => run.solutions.iter().any(|s| s.answer.is_err()),
+        Err(_) => true,
     }
 }
 
-impl From<String> for Result {
-    fn from(x: String) -> Self {
-        Self::String(x)
+fn print_records(format: Format, records: &[Record]) {
+    match format {
+        Format::Json => println!("{}", report::json(records)),
+        Format::Markdown => print!("{}", report::markdown(records)),
+        Format::Text => unreachable!("text output is printed by each command"),
     }
 }
 
-fn print_result(result: Result) {
-    println!("-----------------------------");
-    println!("Resunt is: {}", result);
-    println!("-----------------------------")
+fn run_calendar(year: u16) -> Vec<(u8, &'static str, Result<Run, AocError>)> {
+    solvers::calendar(year)
+        .map(|puzzle| (puzzle.day(), puzzle.title(), runner::try_run(puzzle)))
+        .collect()
 }
 
-macro_rules! solve {
-    ($day: path, $input: expr) => {{
-        use $day::*;
-        print_result(solve_part_one($input).into());
-        print_result(solve_part_two($input).into());
-    }};
+fn calendar_records(year: u16, runs: &[(u8, &'static str, Result<Run, AocError>)]) -> Vec<Record> {
+    runs.iter()
+        .flat_map(|(day, title, run)| report::records(year, *day, title, run))
+        .collect()
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let day: u8 = args[1].clone().parse().unwrap();
-    let input = read_lines(format!("input{:02}.in", day).as_str()).unwrap();
-
-    match day {
-        1 => solve!(solvers::day01, &input),
-        2 => solve!(solvers::day02, &input),
-        3 => solve!(solvers::day03, &input),
-        4 => solve!(solvers::day04, &input),
-        5 => solve!(solvers::day05, &input),
-        6 => solve!(solvers::day06, &input),
-        7 => solve!(solvers::day07, &input),
+fn run_all(year: u16, format: Format) -> Result<(), AocError> {
+    let runs = run_calendar(year);
+    match format {
+        Format::Text => print_summary(&runs),
+        _ => print_records(format, &calendar_records(year, &runs)),
+    }
+    if runs.iter().any(|(_, _, run)| failed(run)) {
+        process::exit(1);
+    }
+    Ok(())
+}
+
+/// Rewrites the README with a progress table of the year's days, starring
+/// the parts whose answers match `answers.toml`.
+fn run_readme(year: u16) -> Result<(), AocError> {
+    let records = calendar_records(year, &run_calendar(year));
+    let path = config::Config::load()?.root()?.join("README");
+    let readme = format!(
+        "Advent of Code {} done in Rust.\n\n{}",
+        year,
+        report::progress(&records, &Answers::load(year)?)
+    );
+    fs::write(&path, readme).map_err(|e| AocError::Write(path.display().to_string(), e))?;
+    println!("wrote {}", path.display());
+    Ok(())
+}
+
+struct RunOptions {
+    source: InputSource,
+    parts: Vec<Part>,
+    params: Vec<(String, String)>,
+}
+
+fn parse_run_options(args: &[String]) -> Result<RunOptions, String> {
+    let mut options = RunOptions {
+        source: InputSource::Inputs,
+        parts: Part::ALL.to_vec(),
+        params: Vec::new(),
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        let source = match arg.as_str() {
+            "--input" | "-i" => match value()?.as_str() {
+                "-" => InputSource::Stdin,
+                path => InputSource::Path(path.into()),
+            },
+            "--stdin" => InputSource::Stdin,
+            "--text" | "-t" => InputSource::Text(value()?.clone()),
+            "--part" | "-p" => {
+                options.parts = vec![value()?.parse()?];
+                continue;
+            }
+            "--param" => {
+                let param = solvers::parse_assignment(value()?).map_err(|e| e.to_string())?;
+                options.params.push(param);
+                continue;
+            }
+            _ => return Err(format!("unknown argument {:?}", arg)),
+        };
+        if options.source != InputSource::Inputs {
+            return Err("only one of --input, --stdin and --text can be given".to_string());
+        }
+        options.source = source;
+    }
+    Ok(options)
+}
+
+fn run_day(year: u16, day: u8, format: Format, args: &[String]) -> Result<(), AocError> {
+    let options = parse_run_options(args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!(
+            "usage: <day> [--part 1|2] [--param NAME=VALUE]... [--input PATH | --stdin | --text INPUT]"
+        );
+        process::exit(2);
+    });
+    let solver = solvers::find(year, day)?;
+    let params = Params::new(solver.params(), options.params)?;
+    let input = options.source.read(year, day)?;
+    let run = runner::run(solver, &input, &options.parts, &params);
+    if format != Format::Text {
+        print_records(format, &report::records(year, day, solver.title(), &run));
+        if failed(&run) {
+            process::exit(1);
+        }
+        return Ok(());
+    }
+    let run = run?;
+    println!("Day {}: {}", run.day, run.title);
+    println!("Parsed in {:?}", run.parse_time);
+    let mut errors = Vec::new();
+    for solution in run.solutions {
+        match solution.answer {
+            Ok(answer) => print_result(answer, solution.elapsed),
+            Err(e) => errors.push(e),
+        }
+    }
+    match errors.into_iter().next() {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
+}
+
+struct BenchOptions {
+    days: Vec<u8>,
+    runs: usize,
+    save: Option<PathBuf>,
+    baseline: Option<PathBuf>,
+    threshold: f64,
+}
+
+fn parse_bench_options(year: u16, args: &[String]) -> Result<BenchOptions, String> {
+    let mut options = BenchOptions {
+        days: Vec::new(),
+        runs: 100,
+        save: None,
+        baseline: None,
+        threshold: 10.0,
+    };
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or(format!("missing value for {}", arg));
+        match arg.as_str() {
+            "--runs" | "-n" => options.runs = value()?.parse().map_err(|e| format!("{}", e))?,
+            "--save" => options.save = Some(value()?.into()),
+            "--baseline" => options.baseline = Some(value()?.into()),
+            "--threshold" => options.threshold = value()?.parse().map_err(|e| format!("{}", e))?,
+            "all" => options
+                .days
+                .extend(solvers::calendar(year).map(|s| s.day())),
+            day => options.days.push(
+                day.parse()
+                    .map_err(|_| format!("unknown argument {:?}", day))?,
+            ),
+        }
+    }
+    if options.days.is_empty() {
+        return Err("no day to bench".to_string());
+    }
+    if options.runs == 0 {
+        return Err("--runs must be positive".to_string());
+    }
+    Ok(options)
+}
+
+fn run_bench(year: u16, args: &[String]) -> Result<(), AocError> {
+    let options = parse_bench_options(year, args).unwrap_or_else(|e| {
+        eprintln!("{}", e);
+        eprintln!(
+            "usage: bench <day|all>... [--runs N] [--save FILE] [--baseline FILE] [--threshold PCT]"
+        );
+        process::exit(2);
+    });
+    let baseline = options
+        .baseline
+        .as_deref()
+        .map(bench::load_baseline)
+        .transpose()?;
+
+    let mut measurements = Vec::new();
+    for day in &options.days {
+        let puzzle = solvers::find(year, *day)?;
+        match read_input(year, *day).and_then(|input| bench::bench(puzzle, &input, options.runs)) {
+            Ok(m) => measurements.extend(m),
+            Err(e) => eprintln!("skipping day {}: {}", day, e),
+        }
+    }
+
+    let mut header = vec!["Day", "Part", "Min", "Mean", "Median", "Stddev"];
+    if baseline.is_some() {
+        header.push("Baseline");
+    }
+    let mut rows = vec![header.into_iter().map(String::from).collect::<Vec<_>>()];
+    let mut regressions = 0;
+    for m in &measurements {
+        let mut row = vec![
+            m.day.to_string(),
+            m.part.to_string(),
+            format!("{:?}", m.stats.min),
+            format!("{:?}", m.stats.mean),
+            format!("{:?}", m.stats.median),
+            format!("{:?}", m.stats.stddev),
+        ];
+        if let Some(baseline) = &baseline {
+            row.push(match baseline.get(&(m.year, m.day, m.part)) {
+                Some(previous) => {
+                    let change = bench::change(m.stats.mean, *previous) * 100.0;
+                    if change > options.threshold {
+                        regressions += 1;
+                        format!("{:+.1}% REGRESSION", change)
+                    } else {
+                        format!("{:+.1}%", change)
+                    }
+                }
+                None => "-".to_string(),
+            });
+        }
+        rows.push(row);
+    }
+    print_table(&rows, &[6]);
+
+    if let Some(path) = &options.save {
+        bench::save_baseline(path, &measurements)?;
+    }
+    if regressions > 0 {
+        eprintln!("{} regression(s) over {}%", regressions, options.threshold);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn run_verify(year: u16, args: &[String]) -> Result<(), AocError> {
+    let mut days = Vec::new();
+    let mut record = false;
+    for arg in args {
+        match arg.as_str() {
+            "--record" => record = true,
+            "all" => days.extend(solvers::calendar(year).map(|s| s.day())),
+            day => days.push(day.parse().unwrap_or_else(|_| {
+                eprintln!("unknown argument {:?}", day);
+                eprintln!("usage: verify [day|all]... [--record]");
+                process::exit(2);
+            })),
+        }
+    }
+    if days.is_empty() {
+        days.extend(solvers::calendar(year).map(|s| s.day()));
+    }
+
+    let mut answers = Answers::load(year)?;
+    let mut rows = vec![vec![
+        "Day".to_string(),
+        "Part".to_string(),
+        "Status".to_string(),
+        "Answer".to_string(),
+        "Expected".to_string(),
+    ]];
+    let mut failures = 0;
+    let mut recorded = 0;
+    for day in days {
+        let puzzle = solvers::find(year, day)?;
+        let solutions = match runner::try_run(puzzle) {
+            Ok(run) => run.solutions,
+            Err(e) => {
+                failures += 1;
+                rows.push(vec![
+                    day.to_string(),
+                    "-".to_string(),
+                    "ERROR".to_string(),
+                    e.to_string(),
+                ]);
+                continue;
+            }
+        };
+        for solution in solutions {
+            let answer = match solution.answer {
+                Ok(answer) => answer.to_string(),
+                Err(e) => {
+                    failures += 1;
+                    rows.push(vec![
+                        day.to_string(),
+                        solution.part.to_string(),
+                        "ERROR".to_string(),
+                        e.to_string(),
+                    ]);
+                    continue;
+                }
+            };
+            let (status, expected) = match answers.check(day, solution.part, &answer) {
+                Verdict::Pass => ("pass", String::new()),
+                Verdict::Fail { expected } => {
+                    failures += 1;
+                    ("FAIL", expected)
+                }
+                Verdict::Missing if record => {
+                    answers.insert(day, solution.part, answer.clone());
+                    recorded += 1;
+                    ("recorded", String::new())
+                }
+                Verdict::Missing => ("missing", String::new()),
+            };
+            rows.push(vec![
+                day.to_string(),
+                solution.part.to_string(),
+                status.to_string(),
+                answer,
+                expected,
+            ]);
+        }
+    }
+    print_table(&rows, &[2, 3, 4]);
+
+    if recorded > 0 {
+        answers.save(year)?;
+        println!(
+            "recorded {} answer(s) in {}",
+            recorded,
+            Answers::path(year)?.display()
+        );
+    }
+    if failures > 0 {
+        eprintln!("{} failure(s)", failures);
+        process::exit(1);
+    }
+    Ok(())
+}
+
+fn parse_day(day: &str) -> Result<u8, AocError> {
+    day.parse()
+        .map_err(|_| AocError::Argument(format!("{:?} is not a day", day)))
+}
+
+fn run_new(year: u16, args: &[String]) -> Result<(), AocError> {
+    let (day, title) = match args {
+        [day] => (day, None),
+        [day, flag, title] if flag == "--title" => (day, Some(title.clone())),
+        _ => {
+            eprintln!("usage: new <day> [--title TITLE]");
+            process::exit(2);
+        }
+    };
+    let day = parse_day(day)?;
+    let title = title.unwrap_or_else(|| format!("Day {}", day));
+    for path in scaffold::new_day(year, day, &title)? {
+        println!("wrote {}", path.display());
+    }
+    Ok(())
+}
+
+fn run_fetch(year: u16, args: &[String]) -> Result<(), AocError> {
+    if args.is_empty() {
+        eprintln!("usage: fetch <day>...");
+        process::exit(2);
+    }
+    let config = config::Config::load()?;
+    let client = client::Client::from_config(&config)?;
+    let inputs = config.year_dir(year)?;
+    for day in args {
+        let day = parse_day(day)?;
+        match client::fetch_input(&client, &inputs, year, day)? {
+            (path, true) => println!("downloaded {}", path.display()),
+            (path, false) => println!("{} is already cached", path.display()),
+        }
+    }
+    Ok(())
+}
+
+fn run_submit(year: u16, args: &[String]) -> Result<(), AocError> {
+    let (day, part, answer) = match args {
+        [day, part] => (day, part, None),
+        [day, part, answer] => (day, part, Some(answer.clone())),
+        _ => {
+            eprintln!("usage: submit <day> <part> [ANSWER]");
+            process::exit(2);
+        }
+    };
+    let day = parse_day(day)?;
+    let part: Part = part.parse().map_err(AocError::Argument)?;
+    let answer = match answer {
+        Some(answer) => answer,
+        None => {
+            let puzzle = solvers::find(year, day)?;
+            let params = Params::defaults(puzzle.params());
+            let run = runner::run(puzzle, &read_input(year, day)?, &[part], &params)?;
+            let solution = run.solutions.into_iter().next().unwrap();
+            solution.answer?.to_string()
+        }
+    };
+
+    let mut guesses = submit::Guesses::load(year)?;
+    match guesses.check(day, part, &answer) {
+        submit::Check::New => {}
+        submit::Check::Known(outcome) => {
+            return Err(AocError::Solve(format!(
+                "{} was already submitted for day {} part {} and was {}",
+                answer, day, part, outcome
+            )))
+        }
+        submit::Check::Solved(correct) => {
+            return Err(AocError::Solve(format!(
+                "day {} part {} is already solved with {}",
+                day, part, correct
+            )))
+        }
+        submit::Check::AboveTooHigh(bound) => {
+            eprintln!(
+                "warning: {} is not below {}, which was too high",
+                answer, bound
+            )
+        }
+        submit::Check::BelowTooLow(bound) => {
+            eprintln!(
+                "warning: {} is not above {}, which was too low",
+                answer, bound
+            )
+        }
+    }
+
+    let client = client::Client::from_config(&config::Config::load()?)?;
+    // make sure the verdict can be kept before the answer is spent
+    guesses.save(year)?;
+    println!(
+        "submitting {} for day {} of {} part {}",
+        answer, day, year, part
+    );
+    match submit::parse_response(&client.submit(year, day, part, &answer)?)? {
+        submit::Response::Outcome(outcome) => {
+            guesses.insert(day, part, answer.clone(), outcome);
+            guesses.save(year)?;
+            println!("{}", outcome);
+            if outcome == submit::Outcome::Correct {
+                let mut answers = Answers::load(year)?;
+                if answers.get(day, part).is_none() {
+                    answers.insert(day, part, answer);
+                    answers.save(year)?;
+                }
+            } else {
+                process::exit(1);
+            }
+        }
+        submit::Response::RateLimited(wait) => {
+            eprintln!("answered too recently, wait {:?} before trying again", wait);
+            process::exit(1);
+        }
+        submit::Response::WrongLevel => {
+            eprintln!(
+                "day {} part {} cannot be submitted, is it already solved?",
+                day, part
+            );
+            process::exit(1);
+        }
+    }
+    Ok(())
+}
+
+fn run_examples(year: u16, args: &[String]) -> Result<(), AocError> {
+    let force = args.iter().any(|a| a == "--force");
+    let args: Vec<&String> = args.iter().filter(|a| *a != "--force").collect();
+    let (day, page) = match args[..] {
+        [day, page] => (day, page),
         _ => {
-            unimplemented!("yet");
+            eprintln!("usage: examples <day> <PAGE.html> [--force]");
+            process::exit(2);
+        }
+    };
+    let day = parse_day(day)?;
+    let html = std::fs::read_to_string(page).map_err(|e| AocError::Read(page.clone(), e))?;
+    let fixtures = extract::extract(year, day, &html)?;
+    let inputs = config::Config::load()?.year_dir(year)?;
+    let written = extract::write_fixtures(&inputs, &fixtures, force)?;
+    for path in &written {
+        println!("wrote {}", path.display());
+    }
+    if written.is_empty() {
+        println!("the fixtures are up to date");
+    }
+    Ok(())
+}
+
+/// Reads commands for the day 7 shell until `exit` or the end of stdin.
+fn run_shell(mut shell: Shell) -> Result<(), AocError> {
+    println!("type help for the commands, exit to leave");
+    let stdin = io::stdin();
+    loop {
+        print!("{}", shell.prompt());
+        io::stdout().flush()?;
+        let mut line = String::new();
+        if stdin.read_line(&mut line)? == 0 {
+            println!();
+            return Ok(());
+        }
+        if matches!(line.trim(), "exit" | "quit") {
+            return Ok(());
+        }
+        match shell.execute(&line) {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => println!("{}", output),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+}
+
+/// Prints a day 7 transcript of a real directory, optionally checking that
+/// parsing it gives back the sizes on disk.
+fn run_generate(args: &[String]) -> Result<(), AocError> {
+    let mut root = None;
+    let mut seed = None;
+    let mut check = false;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" => {
+                seed = args
+                    .next()
+                    .and_then(|s| s.parse().ok())
+                    .or_else(|| usage_generate())
+            }
+            "--check" => check = true,
+            dir if root.is_none() => root = Some(PathBuf::from(dir)),
+            _ => usage_generate(),
+        }
+    }
+    let root = root.unwrap_or_else(|| usage_generate());
+    let transcript = generate::transcript(&root, seed)?;
+    print!("{}", transcript);
+    if check {
+        let checked = generate::check(&root, &transcript.parse()?)?;
+        eprintln!("{} directories match the disk", checked);
+    }
+    Ok(())
+}
+
+fn usage_generate() -> ! {
+    eprintln!("usage: fs generate <DIR> [--seed N] [--check]");
+    process::exit(2);
+}
+
+/// Shows or explores the filesystem reconstructed from a day 7 transcript.
+fn run_fs(args: &[String]) -> Result<(), AocError> {
+    let usage = || -> ! {
+        eprintln!(
+            "usage: fs <listing|tree|du|json|dot|shell> [--param NAME=VALUE]... \
+             [--input PATH | --stdin | --text INPUT]\n       fs generate <DIR> [--seed N] [--check]"
+        );
+        process::exit(2);
+    };
+    if args.first().map(String::as_str) == Some("generate") {
+        return run_generate(&args[1..]);
+    }
+    let (view, options) = match args.split_first() {
+        Some((view, rest)) => (view, parse_run_options(rest).unwrap_or_else(|_| usage())),
+        None => usage(),
+    };
+    // `None` is the shell
+    let render: Option<fn(&Filesystem) -> String> = match view.as_str() {
+        "listing" => Some(Filesystem::listing),
+        "tree" => Some(Filesystem::tree),
+        "du" => Some(Filesystem::du),
+        "json" => Some(Filesystem::to_json),
+        "dot" => Some(Filesystem::to_dot),
+        "shell" => None,
+        _ => usage(),
+    };
+    if render.is_none() && options.source == InputSource::Stdin {
+        return Err(AocError::Config(
+            "the shell reads its commands from stdin, pass the transcript with --input".to_string(),
+        ));
+    }
+    let solver = solvers::find(solvers::DEFAULT_YEAR, 7)?;
+    let params = Params::new(solver.params(), options.params)?;
+    let input = options.source.read(solvers::DEFAULT_YEAR, 7)?;
+    let filesystem: Filesystem = input.parse()?;
+    match render {
+        Some(render) => println!("{}", render(&filesystem).trim_end()),
+        None => {
+            let shell = Shell::new(filesystem, params.get("capacity")?, params.get("needed")?);
+            run_shell(shell)?;
+        }
+    }
+    Ok(())
+}
+
+/// Takes the global options off the front of the arguments. They must come
+/// before the command, so anything after it is left for the command.
+fn take_globals(args: &mut Vec<String>) -> Result<(u16, Format), AocError> {
+    let mut year = solvers::DEFAULT_YEAR;
+    let mut format = Format::Text;
+    while let Some(option) = args.get(1).cloned() {
+        if !matches!(option.as_str(), "--year" | "-y" | "--format" | "-f") {
+            break;
+        }
+        let value = args
+            .get(2)
+            .cloned()
+            .ok_or_else(|| AocError::Argument(format!("missing value for {}", option)))?;
+        if option == "--year" || option == "-y" {
+            year = value
+                .parse()
+                .map_err(|_| AocError::Argument(format!("{:?} is not a year", value)))?;
+        } else {
+            format = value.parse().map_err(AocError::Argument)?;
+        }
+        args.drain(1..3);
+    }
+    Ok((year, format))
+}
+
+fn main() {
+    let mut args: Vec<String> = env::args().collect();
+    let (year, format) = take_globals(&mut args).unwrap_or_else(|e| {
+        eprintln!("error: {}", e);
+        process::exit(2);
+    });
+    // `--day N` is the long form of a bare day number
+    if args.get(1).map(String::as_str) == Some("--day") {
+        args.remove(1);
+    }
+    let result = match args.get(1).map(String::as_str) {
+        // only day runs and `all` can print anything but text
+        Some(
+            command @ ("readme" | "bench" | "verify" | "new" | "fetch" | "submit" | "examples"
+            | "fs" | "watch"),
+        ) if format != Format::Text => Err(AocError::Argument(format!(
+            "{} only prints text, --format is for day runs and all",
+            command
+        ))),
+        Some("all") => run_all(year, format),
+        Some("readme") => run_readme(year),
+        Some("bench") => run_bench(year, &args[2..]),
+        Some("verify") => run_verify(year, &args[2..]),
+        Some("new") => run_new(year, &args[2..]),
+        Some("fetch") => run_fetch(year, &args[2..]),
+        Some("submit") => run_submit(year, &args[2..]),
+        Some("examples") => run_examples(year, &args[2..]),
+        Some("fs") => run_fs(&args[2..]),
+        Some("watch") => match args.get(2) {
+            Some(day) => parse_day(day).and_then(|day| watch::watch(year, day)),
+            None => {
+                eprintln!("usage: watch <day>");
+                process::exit(2);
+            }
+        },
+        Some(day) => parse_day(day).and_then(|day| run_day(year, day, format, &args[2..])),
+        None => {
+            eprintln!(
+                "usage: {} [--year YEAR] [--format text|json|markdown] <[--day] day|all|bench|verify|new|fetch|submit|examples|watch|readme|fs>",
+                args[0]
+            );
+            process::exit(2);
         }
+    };
+    if let Err(e) = result {
+        eprintln!("error: {}", e);
+        process::exit(if matches!(e, AocError::Argument(_)) {
+            2
+        } else {
+            1
+        });
     }
 }
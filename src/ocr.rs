@@ -0,0 +1,174 @@
+use std::collections::HashMap;
+
+const LIT: char = '#';
+
+const FONT: &[(char, [&str; 6])] = &[
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('I', [".###", "..#.", "..#.", "..#.", "..#.", ".###"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Y', ["#...#", "#...#", ".#.#.", "..#..", "..#..", "..#.."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+const HEIGHT: usize = 6;
+
+/// A picture answer, as drawn by the puzzles that spell their answer out
+/// with lit pixels.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Grid {
+    rows: Vec<Vec<bool>>,
+}
+
+impl Grid {
+    pub fn new(rows: Vec<Vec<bool>>) -> Self {
+        Self { rows }
+    }
+
+    /// Builds a grid from text where `#` is a lit pixel and anything else is dark.
+    pub fn from_pixels(s: &str) -> Self {
+        Self::new(
+            s.lines()
+                .map(|line| line.chars().map(|c| c == LIT).collect())
+                .collect(),
+        )
+    }
+
+    pub(crate) fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    pub(crate) fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    fn lit(&self, row: usize, column: usize) -> bool {
+        self.rows[row].get(column).copied().unwrap_or(false)
+    }
+
+    pub fn render(&self) -> String {
+        self.rows
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&lit| if lit { '█' } else { ' ' })
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+
+    /// Reads the letters drawn in the grid. Glyphs are separated by dark
+    /// columns; `None` is returned if any of them is not in the font.
+    pub fn ocr(&self) -> Option<String> {
+        if self.rows.len() != HEIGHT {
+            return None;
+        }
+        let font: HashMap<Vec<String>, char> = FONT
+            .iter()
+            .map(|(letter, glyph)| (glyph.iter().map(|row| row.to_string()).collect(), *letter))
+            .map(|(glyph, letter)| (trim_glyph(glyph), letter))
+            .collect();
+
+        let mut text = String::new();
+        let mut column = 0;
+        let width = self.width();
+        while column < width {
+            if (0..HEIGHT).all(|row| !self.lit(row, column)) {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < width && (0..HEIGHT).any(|row| self.lit(row, column)) {
+                column += 1;
+            }
+            let glyph: Vec<String> = (0..HEIGHT)
+                .map(|row| {
+                    (start..column)
+                        .map(|c| if self.lit(row, c) { LIT } else { '.' })
+                        .collect()
+                })
+                .collect();
+            text.push(*font.get(&glyph)?);
+        }
+        if text.is_empty() {
+            None
+        } else {
+            Some(text)
+        }
+    }
+}
+
+fn trim_glyph(glyph: Vec<String>) -> Vec<String> {
+    let width = glyph[0].len();
+    let blank = |c: usize| glyph.iter().all(|row| row.as_bytes()[c] != LIT as u8);
+    let start = (0..width).find(|&c| !blank(c)).unwrap_or(0);
+    let end = (0..width).rev().find(|&c| !blank(c)).map_or(0, |c| c + 1);
+    glyph
+        .iter()
+        .map(|row| row[start..end].to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn draw(text: &str) -> Grid {
+        let glyphs: Vec<&[&str; 6]> = text
+            .chars()
+            .map(|c| &FONT.iter().find(|(letter, _)| *letter == c).unwrap().1)
+            .collect();
+        let pixels: Vec<String> = (0..HEIGHT)
+            .map(|row| glyphs.iter().map(|g| format!("{}.", g[row])).collect())
+            .collect();
+        Grid::from_pixels(&pixels.join("\n"))
+    }
+
+    #[test]
+    fn test_ocr_every_letter() {
+        let alphabet: String = FONT.iter().map(|(letter, _)| *letter).collect();
+        assert_eq!(draw(&alphabet).ocr(), Some(alphabet));
+    }
+
+    #[test]
+    fn test_ocr_puzzle_output() {
+        let grid = Grid::from_pixels(
+            "\
+###..#....####.####.#..#.#....
+#..#.#....#.......#.#..#.#....
+#..#.#....###....#..####.#....
+###..#....#.....#...#..#.#....
+#.#..#....#....#....#..#.#....
+#..#.####.####.####.#..#.####.",
+        );
+        assert_eq!(grid.ocr(), Some("RLEZHL".to_string()));
+    }
+
+    #[test]
+    fn test_ocr_unknown_glyph() {
+        let grid = Grid::from_pixels("#.#\n.#.\n#.#\n.#.\n#.#\n.#.");
+        assert_eq!(grid.ocr(), None);
+        assert_eq!(Grid::from_pixels("#..#").ocr(), None);
+    }
+
+    #[test]
+    fn test_render() {
+        let grid = Grid::from_pixels("#.#.\n.#..");
+        assert_eq!(grid.render(), "█ █\n █");
+    }
+}
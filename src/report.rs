@@ -0,0 +1,266 @@
+use std::{str::FromStr, time::Duration};
+
+use aoc22::{error::AocError, runner::Run, solvers::Part};
+use serde_json::json;
+
+use crate::answers::{Answers, Verdict};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    Text,
+    Json,
+    Markdown,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "markdown" | "md" => Ok(Format::Markdown),
+            _ => Err(format!(
+                "unknown format {:?}, expected text, json or markdown",
+                s
+            )),
+        }
+    }
+}
+
+/// One solved part, or a whole day when it failed before reaching the parts.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub part: Option<Part>,
+    pub answer: Result<String, String>,
+    pub parse_time: Option<Duration>,
+    pub solve_time: Option<Duration>,
+}
+
+impl Record {
+    fn status(&self) -> &'static str {
+        match self.answer {
+            Ok(_) => "ok",
+            Err(_) => "error",
+        }
+    }
+}
+
+/// Flattens a day's run into one record per part.
+pub fn records(
+    year: u16,
+    day: u8,
+    title: &'static str,
+    run: &Result<Run, AocError>,
+) -> Vec<Record> {
+    match run {
+        Ok(run) => run
+            .solutions
+            .iter()
+            .map(|solution| Record {
+                year: run.year,
+                day: run.day,
+                title: run.title,
+                part: Some(solution.part),
+                answer: match &solution.answer {
+                    Ok(answer) => Ok(answer.to_string()),
+                    Err(e) => Err(e.to_string()),
+                },
+                parse_time: Some(run.parse_time),
+                solve_time: Some(solution.elapsed),
+            })
+            .collect(),
+        Err(e) => vec![Record {
+            year,
+            day,
+            title,
+            part: None,
+            answer: Err(e.to_string()),
+            parse_time: None,
+            solve_time: None,
+        }],
+    }
+}
+
+fn number(part: Part) -> u8 {
+    match part {
+        Part::One => 1,
+        Part::Two => 2,
+    }
+}
+
+/// A JSON array with one object per record. Times are in nanoseconds.
+pub fn json(records: &[Record]) -> String {
+    let records: Vec<serde_json::Value> = records
+        .iter()
+        .map(|r| {
+            json!({
+                "year": r.year,
+                "day": r.day,
+                "title": r.title,
+                "part": r.part.map(number),
+                "answer": r.answer.as_ref().ok(),
+                "error": r.answer.as_ref().err(),
+                "parse_time_ns": r.parse_time.map(|t| t.as_nanos() as u64),
+                "solve_time_ns": r.solve_time.map(|t| t.as_nanos() as u64),
+                "status": r.status(),
+            })
+        })
+        .collect();
+    serde_json::to_string_pretty(&records).unwrap()
+}
+
+fn cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+fn time(t: Option<Duration>) -> String {
+    t.map_or_else(String::new, |t| format!("{:?}", t))
+}
+
+pub fn markdown(records: &[Record]) -> String {
+    let mut table = String::from(
+        "| Year | Day | Title | Part | Answer | Parse | Solve | Status |\n\
+         |-----:|----:|:------|-----:|:-------|------:|------:|:-------|\n",
+    );
+    for r in records {
+        table.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} | {} | {} |\n",
+            r.year,
+            r.day,
+            cell(r.title),
+            r.part.map_or_else(|| "-".to_string(), |p| p.to_string()),
+            cell(match &r.answer {
+                Ok(answer) => answer,
+                Err(e) => e,
+            }),
+            time(r.parse_time),
+            time(r.solve_time),
+            r.status(),
+        ));
+    }
+    table
+}
+
+/// Progress table for the README: a star per part whose answer matches the
+/// recorded one in `answers`, without the answers themselves.
+pub fn progress(records: &[Record], answers: &Answers) -> String {
+    let mut table =
+        String::from("| Day | Title | Part 1 | Part 2 |\n|----:|:------|:------:|:------:|\n");
+    let mut days: Vec<(u8, &str)> = records.iter().map(|r| (r.day, r.title)).collect();
+    days.dedup();
+    for (day, title) in days {
+        let star = |part: Part| {
+            let solved = records.iter().any(|r| {
+                r.day == day
+                    && r.part == Some(part)
+                    && matches!(&r.answer, Ok(answer) if answers.check(day, part, answer) == Verdict::Pass)
+            });
+            if solved {
+                "⭐"
+            } else {
+                ""
+            }
+        };
+        table.push_str(&format!(
+            "| {} | {} | {} | {} |\n",
+            day,
+            cell(title),
+            star(Part::One),
+            star(Part::Two)
+        ));
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Vec<Record> {
+        vec![
+            Record {
+                year: 2022,
+                day: 5,
+                title: "Supply Stacks",
+                part: Some(Part::One),
+                answer: Ok("CMZ".to_string()),
+                parse_time: Some(Duration::from_micros(3)),
+                solve_time: Some(Duration::from_nanos(1500)),
+            },
+            Record {
+                year: 2022,
+                day: 5,
+                title: "Supply Stacks",
+                part: Some(Part::Two),
+                answer: Err("cannot solve: a|b".to_string()),
+                parse_time: Some(Duration::from_micros(3)),
+                solve_time: Some(Duration::from_nanos(20)),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse(), Ok(Format::Json));
+        assert_eq!("md".parse(), Ok(Format::Markdown));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        let value: serde_json::Value = serde_json::from_str(&json(&sample())).unwrap();
+        assert_eq!(
+            value[0],
+            json!({
+                "year": 2022,
+                "day": 5,
+                "title": "Supply Stacks",
+                "part": 1,
+                "answer": "CMZ",
+                "error": null,
+                "parse_time_ns": 3000,
+                "solve_time_ns": 1500,
+                "status": "ok",
+            })
+        );
+        assert_eq!(value[1]["status"], "error");
+        assert_eq!(value[1]["answer"], serde_json::Value::Null);
+    }
+
+    #[test]
+    fn test_markdown() {
+        let table = markdown(&sample());
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "| 2022 | 5 | Supply Stacks | 1 | CMZ | 3µs | 1.5µs | ok |"
+        );
+        assert!(lines[3].contains("cannot solve: a\\|b"));
+    }
+
+    #[test]
+    fn test_progress() {
+        let header = "| Day | Title | Part 1 | Part 2 |\n|----:|:------|:------:|:------:|\n";
+        let mut answers = Answers::default();
+        assert_eq!(
+            progress(&sample(), &answers),
+            format!("{}| 5 | Supply Stacks |  |  |\n", header)
+        );
+        answers.insert(5, Part::One, "MCD".to_string());
+        answers.insert(5, Part::Two, "MCD".to_string());
+        assert_eq!(
+            progress(&sample(), &answers),
+            format!("{}| 5 | Supply Stacks |  |  |\n", header)
+        );
+        answers.insert(5, Part::One, "CMZ".to_string());
+        assert_eq!(
+            progress(&sample(), &answers),
+            format!("{}| 5 | Supply Stacks | ⭐ |  |\n", header)
+        );
+    }
+}
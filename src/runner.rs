@@ -0,0 +1,122 @@
+use std::{
+    fs,
+    io::{self, Read},
+    panic::{self, AssertUnwindSafe},
+    path::PathBuf,
+    time::Duration,
+};
+
+use crate::{
+    config::Config,
+    error::AocError,
+    solvers::{timed, Params, Part, Puzzle},
+    Answer,
+};
+
+pub struct Solution {
+    pub part: Part,
+    pub answer: Result<Answer, AocError>,
+    pub elapsed: Duration,
+}
+
+pub struct Run {
+    pub year: u16,
+    pub day: u8,
+    pub title: &'static str,
+    pub parse_time: Duration,
+    pub solutions: Vec<Solution>,
+}
+
+pub fn input_file(day: u8) -> String {
+    format!("input{:02}.in", day)
+}
+
+/// The day's example fixture, see [`crate::fixtures::Fixture`].
+pub fn example_file(day: u8) -> String {
+    format!("example{:02}.toml", day)
+}
+
+pub fn read_input(year: u16, day: u8) -> Result<String, AocError> {
+    let path = Config::load()?.year_dir(year)?.join(input_file(day));
+    fs::read_to_string(&path).map_err(|e| AocError::Read(path.display().to_string(), e))
+}
+
+/// Where a single run takes its puzzle input from.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// `inputNN.in` in the year's inputs directory.
+    Inputs,
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    pub fn read(&self, year: u16, day: u8) -> Result<String, AocError> {
+        match self {
+            InputSource::Inputs => read_input(year, day),
+            InputSource::Path(path) => {
+                fs::read_to_string(path).map_err(|e| AocError::Read(path.display().to_string(), e))
+            }
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| AocError::Read("stdin".to_string(), e))?;
+                Ok(input)
+            }
+            InputSource::Text(text) => Ok(text.clone()),
+        }
+    }
+}
+
+pub fn run(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    parts: &[Part],
+    params: &Params,
+) -> Result<Run, AocError> {
+    let (parsed, parse_time) = timed(|| puzzle.parse(input));
+    let parsed = parsed?;
+    let solutions = parts
+        .iter()
+        .map(|&part| {
+            let (answer, elapsed) = timed(|| parsed.solve(part, params));
+            Solution {
+                part,
+                answer,
+                elapsed,
+            }
+        })
+        .collect();
+    Ok(Run {
+        year: puzzle.year(),
+        day: puzzle.day(),
+        title: puzzle.title(),
+        parse_time,
+        solutions,
+    })
+}
+
+/// Reads the day's input and runs it, turning a panicking solver into an
+/// error as well so that one broken day doesn't stop the others.
+pub fn try_run(puzzle: &dyn Puzzle) -> Result<Run, AocError> {
+    let input = read_input(puzzle.year(), puzzle.day())?;
+    let params = Params::defaults(puzzle.params());
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        run(puzzle, &input, &Part::ALL, &params)
+    }));
+    panic::set_hook(hook);
+    result.unwrap_or_else(|payload| {
+        let message = if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            "no message".to_string()
+        };
+        Err(AocError::Panic(message))
+    })
+}
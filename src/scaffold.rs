@@ -0,0 +1,235 @@
+use std::{fs, path::PathBuf};
+
+use aoc22::{
+    config::Config,
+    error::AocError,
+    runner::{example_file, input_file},
+    solvers::{self, DEFAULT_YEAR},
+};
+
+/// `dayNN` for the default year, `yYYYY_dayNN` for the others.
+pub fn module(year: u16, day: u8) -> String {
+    if year == DEFAULT_YEAR {
+        format!("day{:02}", day)
+    } else {
+        format!("y{}_day{:02}", year, day)
+    }
+}
+
+fn template(year: u16, day: u8, title: &str) -> String {
+    let year = if year == DEFAULT_YEAR {
+        String::new()
+    } else {
+        format!("    fn year(&self) -> u16 {{\n        {}\n    }}\n\n", year)
+    };
+    format!(
+        r#"use super::{{Params, Solver}};
+use crate::{{error::AocError, Answer}};
+
+pub fn solve_part_one(_input: &[String]) -> Result<usize, AocError> {{
+    Err(AocError::Solve("part one is not solved yet".to_string()))
+}}
+
+pub fn solve_part_two(_input: &[String]) -> Result<usize, AocError> {{
+    Err(AocError::Solve("part two is not solved yet".to_string()))
+}}
+
+pub struct Day{day:02};
+
+impl Solver for Day{day:02} {{
+    type Input = Vec<String>;
+
+{year}    fn day(&self) -> u8 {{
+        {day}
+    }}
+
+    fn title(&self) -> &'static str {{
+        {title:?}
+    }}
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {{
+        Ok(input.lines().map(str::to_string).collect())
+    }}
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {{
+        Ok(solve_part_one(input)?.into())
+    }}
+
+    fn part_two(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {{
+        Ok(solve_part_two(input)?.into())
+    }}
+}}
+
+#[cfg(test)]
+mod tests {{
+    use super::*;
+
+    #[test]
+    fn test_parse() {{
+        assert_eq!(Day{day:02}.parse("a\nb").unwrap().len(), 2);
+    }}
+}}
+"#
+    )
+}
+
+const FIXTURE_TEMPLATE: &str = "# part1 = \"\"\n# part2 = \"\"\ninput = '''\n'''\n";
+
+/// Adds the day's `pub mod` declaration and `SOLVERS` entry to the source of
+/// `solvers/mod.rs`, keeping both ordered by day.
+fn register(source: &str, year: u16, day: u8) -> Result<String, AocError> {
+    let module = module(year, day);
+    let declaration = format!("pub mod {};", module);
+    let entry = format!("    &{}::Day{:02},", module, day);
+    if source.lines().any(|line| line == declaration) {
+        return Err(AocError::Config(format!(
+            "{} is already registered",
+            module
+        )));
+    }
+
+    let mut lines: Vec<String> = source.lines().map(str::to_string).collect();
+    let declarations = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| line.starts_with("pub mod ") && line.contains("day"))
+        .map(|(i, line)| (i, line.as_str() < declaration.as_str()))
+        .collect::<Vec<_>>();
+    let at = match declarations.iter().find(|(_, before)| !before) {
+        Some((i, _)) => *i,
+        None => declarations.last().map_or(0, |(i, _)| i + 1),
+    };
+    lines.insert(at, declaration);
+
+    let start = lines
+        .iter()
+        .position(|line| line.starts_with("pub const SOLVERS"))
+        .ok_or_else(|| AocError::Config("solvers/mod.rs has no SOLVERS registry".to_string()))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "];")
+            .ok_or_else(|| AocError::Config("unterminated SOLVERS registry".to_string()))?;
+    let at = (start + 1..end)
+        .find(|&i| lines[i].as_str() > entry.as_str())
+        .unwrap_or(end);
+    lines.insert(at, entry);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+fn create(path: PathBuf, contents: &str, created: &mut Vec<PathBuf>) -> Result<(), AocError> {
+    if !path.exists() {
+        fs::write(&path, contents).map_err(|e| AocError::Write(path.display().to_string(), e))?;
+        created.push(path);
+    }
+    Ok(())
+}
+
+/// Generates the day's solver module, registers it and creates its empty
+/// input and example files. Returns the files that were written.
+pub fn new_day(year: u16, day: u8, title: &str) -> Result<Vec<PathBuf>, AocError> {
+    if solvers::find(year, day).is_ok() {
+        return Err(AocError::Config(format!(
+            "day {} of {} already has a solver",
+            day, year
+        )));
+    }
+    let config = Config::load()?;
+    let solvers_dir = config.root()?.join("src/solvers");
+    let source = solvers_dir.join(format!("{}.rs", module(year, day)));
+    if source.exists() {
+        return Err(AocError::Config(format!(
+            "{} already exists",
+            source.display()
+        )));
+    }
+    let mod_rs = solvers_dir.join("mod.rs");
+    let registry =
+        fs::read_to_string(&mod_rs).map_err(|e| AocError::Read(mod_rs.display().to_string(), e))?;
+    let registry = register(&registry, year, day)?;
+
+    let inputs = config.year_dir(year)?;
+    fs::create_dir_all(&inputs).map_err(|e| AocError::Write(inputs.display().to_string(), e))?;
+
+    let mut created = Vec::new();
+    create(source, &template(year, day, title), &mut created)?;
+    fs::write(&mod_rs, registry).map_err(|e| AocError::Write(mod_rs.display().to_string(), e))?;
+    created.push(mod_rs);
+    create(inputs.join(input_file(day)), "", &mut created)?;
+    create(
+        inputs.join(example_file(day)),
+        FIXTURE_TEMPLATE,
+        &mut created,
+    )?;
+    Ok(created)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "pub mod day01;
+pub mod day03;
+mod params;
+
+pub const SOLVERS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day03::Day03,
+];
+";
+
+    #[test]
+    fn test_register() {
+        assert_eq!(
+            register(REGISTRY, 2022, 2).unwrap(),
+            "pub mod day01;
+pub mod day02;
+pub mod day03;
+mod params;
+
+pub const SOLVERS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+];
+"
+        );
+        let registry = register(REGISTRY, 2022, 12).unwrap();
+        assert!(registry.contains("pub mod day03;\npub mod day12;\nmod params;"));
+        assert!(registry.contains("    &day03::Day03,\n    &day12::Day12,\n];"));
+        let registry = register(REGISTRY, 2023, 1).unwrap();
+        assert!(registry.contains("pub mod day03;\npub mod y2023_day01;\nmod params;"));
+        assert!(registry.contains("    &day03::Day03,\n    &y2023_day01::Day01,\n];"));
+    }
+
+    #[test]
+    fn test_register_errors() {
+        assert!(matches!(
+            register(REGISTRY, 2022, 3),
+            Err(AocError::Config(_))
+        ));
+        assert!(matches!(
+            register("pub mod day01;\n", 2022, 2),
+            Err(AocError::Config(_))
+        ));
+    }
+
+    #[test]
+    fn test_template() {
+        let source = template(2022, 8, "Treetop Tree House");
+        assert!(source.contains("pub struct Day08;"));
+        assert!(source.contains("        8\n"));
+        assert!(source.contains("\"Treetop Tree House\""));
+        assert!(!source.contains("fn year"));
+        assert!(source.contains("\"part one is not solved yet\""));
+        assert!(template(2023, 1, "Trebuchet?!").contains("        2023\n"));
+    }
+
+    #[test]
+    fn test_fixture_template() {
+        let fixture = aoc22::fixtures::Fixture::parse("example08.toml", FIXTURE_TEMPLATE).unwrap();
+        assert_eq!(fixture.input, "");
+        assert!(fixture.expected.is_empty());
+    }
+}
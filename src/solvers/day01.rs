@@ -1,53 +1,88 @@
 use std::{cmp::max, mem::swap};
 
-pub fn solve_part_one(input: &str) -> i32 {
-    let meals: Vec<Option<i32>> = input.lines().map(|i| i.parse().ok()).collect();
+use super::{Param, Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
+
+pub fn solve_part_one(elves_calories: &[i32]) -> i32 {
     let mut most_carried_calories = 0;
-    let mut carried_calories = 0;
-    for meal in meals {
-        if let Some(calories) = meal {
-            carried_calories += calories;
-        } else {
-            most_carried_calories = max(most_carried_calories, carried_calories);
-            carried_calories = 0;
-        }
+    for carried_calories in elves_calories {
+        most_carried_calories = max(most_carried_calories, *carried_calories);
     }
     most_carried_calories
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
-    let meals: Vec<Option<i32>> = input.lines().map(|i| i.parse().ok()).collect();
-    let mut most_carried_calories = [0, 0, 0];
-    let elves_calories: Vec<i32> = meals
-        .split(Option::is_none)
-        .map(|i| i.iter().map(|j| j.unwrap()).sum())
-        .collect();
-    for mut elf_calories in elves_calories {
+pub fn solve_part_two(elves_calories: &[i32], top: usize) -> i32 {
+    let mut most_carried_calories = vec![0; top];
+    for mut elf_calories in elves_calories.iter().copied() {
         for most_carried_cal in most_carried_calories.iter_mut() {
             if elf_calories > *most_carried_cal {
-                swap(&mut elf_calories,  most_carried_cal);
+                swap(&mut elf_calories, most_carried_cal);
             }
         }
     }
     most_carried_calories.iter().sum()
 }
 
+pub struct Day01;
+
+impl Solver for Day01 {
+    type Input = Vec<i32>;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "Calorie Counting"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[Param {
+            name: "top",
+            default: "3",
+            description: "number of elves whose calories are summed in part two",
+        }]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        let meals: Vec<Option<i32>> = input
+            .lines()
+            .enumerate()
+            .map(|(n, i)| match i {
+                "" => Ok(None),
+                _ => i.parse().map(Some).map_err(|e| {
+                    AocError::Parse(format!("{} is not a calorie count: {}", i, e))
+                        .at(Location::line(n + 1, i))
+                }),
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(meals
+            .split(Option::is_none)
+            .map(|i| i.iter().flatten().sum())
+            .collect())
+    }
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input, params.get("top")?).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::read_lines;
-
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example01.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 24000);
-    }
 
     #[test]
-    fn test_part_two() {
-        let input = read_lines("example01.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 45000);
+    fn test_top() {
+        let calories = [6000, 4000, 11000, 24000, 10000];
+        assert_eq!(solve_part_two(&calories, 1), solve_part_one(&calories));
+        assert_eq!(solve_part_two(&calories, 2), 35000);
+        assert_eq!(solve_part_two(&calories, 10), 55000);
     }
 }
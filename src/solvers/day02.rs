@@ -1,3 +1,10 @@
+use std::str::FromStr;
+
+use super::{Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
 
 #[derive(PartialEq, Eq, Clone, Copy)]
 enum Hand {
@@ -12,24 +19,36 @@ enum Outcome {
     Lost,
 }
 
-impl From<&str> for Hand {
-    fn from(str: &str) -> Self {
+/// One line of the strategy guide. The second column is kept in both of its
+/// readings: as our hand for part one and as the desired outcome for part two.
+pub struct Round {
+    opponent: Hand,
+    response: Hand,
+    outcome: Outcome,
+}
+
+impl FromStr for Hand {
+    type Err = AocError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
         match str {
-            "A" | "X" => Hand::Rock,
-            "B" | "Y" => Hand::Paper,
-            "C" | "Z" => Hand::Scissors,
-            _ => panic!("unknown hand shape!"),
+            "A" | "X" => Ok(Hand::Rock),
+            "B" | "Y" => Ok(Hand::Paper),
+            "C" | "Z" => Ok(Hand::Scissors),
+            _ => Err(AocError::Parse(format!("unknown hand shape {:?}", str))),
         }
     }
 }
 
-impl From<&str> for Outcome {
-    fn from(str: &str) -> Self {
+impl FromStr for Outcome {
+    type Err = AocError;
+
+    fn from_str(str: &str) -> Result<Self, Self::Err> {
         match str {
-            "X" => Outcome::Lost,
-            "Y" => Outcome::Draw,
-            "Z" => Outcome::Win,
-            _ => panic!("unknown outcome!"),
+            "X" => Ok(Outcome::Lost),
+            "Y" => Ok(Outcome::Draw),
+            "Z" => Ok(Outcome::Win),
+            _ => Err(AocError::Parse(format!("unknown outcome {:?}", str))),
         }
     }
 }
@@ -86,38 +105,70 @@ impl Hand {
     }
 }
 
-pub fn solve_part_one(input: &str) -> i32 {
-    let lines = input.lines();
-    lines
-        .map(|l| l.split_once(' ').unwrap())
-        .map(|(o, i)| Hand::from(i).round_points(&Hand::from(o)))
+pub fn solve_part_one(rounds: &[Round]) -> i32 {
+    rounds
+        .iter()
+        .map(|r| r.response.round_points(&r.opponent))
         .sum()
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
-    let lines = input.lines();
-    lines
-        .map(|l| l.split_once(' ').unwrap())
-        .map(|(o, i)| Hand::round_points_with_outcome(&Hand::from(o), &Outcome::from(i)))
+pub fn solve_part_two(rounds: &[Round]) -> i32 {
+    rounds
+        .iter()
+        .map(|r| Hand::round_points_with_outcome(&r.opponent, &r.outcome))
         .sum()
 }
 
+pub struct Day02;
+
+impl Solver for Day02 {
+    type Input = Vec<Round>;
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "Rock Paper Scissors"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(n, l)| {
+                let at = |part: &str| Location::within(n + 1, l, part);
+                let (o, i) = l
+                    .split_once(' ')
+                    .ok_or_else(|| AocError::Parse("expected two columns".to_string()).at(at(l)))?;
+                Ok(Round {
+                    opponent: o.parse().map_err(|e: AocError| e.at(at(o)))?,
+                    response: i.parse().map_err(|e: AocError| e.at(at(i)))?,
+                    outcome: i.parse().map_err(|e: AocError| e.at(at(i)))?,
+                })
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::read_lines;
 
     #[test]
-    fn test_part_one() {
-        let input = read_lines("example02.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 15);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let input = read_lines("example02.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 12);
+    fn test_unknown_hand() {
+        let res = Day02.parse("A Y\nD X\n");
+        match res {
+            Err(AocError::At(location, _)) => assert_eq!(location, Location::new(2, 0..1, "D X")),
+            _ => panic!("expected a located error"),
+        }
     }
 }
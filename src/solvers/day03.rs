@@ -2,67 +2,124 @@ use std::collections::{hash_map::RandomState, HashSet};
 
 use itertools::Itertools;
 
+use super::{Param, Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
+
+/// Items are validated to be ASCII letters when the rucksacks are parsed.
 fn priority(ch: &char) -> i32 {
-    if ch.is_alphabetic() {
-        if ch.is_ascii_lowercase() {
-            *ch as i32 - 96
-        } else {
-            *ch as i32 - 38
-        }
+    if ch.is_ascii_lowercase() {
+        *ch as i32 - 96
     } else {
-        panic!("Unknown item type")
+        *ch as i32 - 38
     }
 }
 
-pub fn solve_part_one(input: &str) -> i32 {
-    let lines = input.lines();
+pub struct Rucksack {
+    first: HashSet<char, RandomState>,
+    second: HashSet<char, RandomState>,
+}
+
+impl Rucksack {
+    fn items(&self) -> HashSet<char, RandomState> {
+        self.first.union(&self.second).cloned().collect()
+    }
+}
+
+pub fn solve_part_one(rucksacks: &[Rucksack]) -> i32 {
     let mut priority_sum = 0;
-    for line in lines {
-        let (first_part, second_part) = line.split_at(line.len() / 2);
-        let first: HashSet<char, RandomState> = HashSet::from_iter(first_part.chars());
-        let second: HashSet<char, RandomState> = HashSet::from_iter(second_part.chars());
-        let interection = first.intersection(&second);
+    for rucksack in rucksacks {
+        let interection = rucksack.first.intersection(&rucksack.second);
         priority_sum += interection.map(priority).sum::<i32>()
     }
     priority_sum
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
+pub fn solve_part_two(rucksacks: &[Rucksack], group_size: usize) -> Result<i32, AocError> {
+    if group_size == 0 || !rucksacks.len().is_multiple_of(group_size) {
+        return Err(AocError::Solve(format!(
+            "{} elves cannot be split into groups of {}",
+            rucksacks.len(),
+            group_size
+        )));
+    }
     let mut priority_sum: i32 = 0;
-    for mut group in &input
-        .lines()
-        .map(str::chars)
-        .map(HashSet::from_iter)
-        .chunks(3)
-    {
-        let (g1, g2, g3) = group.next_tuple().unwrap();
-        priority_sum += g1
-            .intersection(&g2)
-            .cloned()
-            .collect::<HashSet<char, RandomState>>()
-            .intersection(&g3)
-            .map(priority)
-            .sum::<i32>();
+    for group in &rucksacks.iter().map(Rucksack::items).chunks(group_size) {
+        let badges = group
+            .reduce(|common, items| common.intersection(&items).cloned().collect())
+            .unwrap_or_default();
+        priority_sum += badges.iter().map(priority).sum::<i32>();
+    }
+    Ok(priority_sum)
+}
+
+pub struct Day03;
+
+impl Solver for Day03 {
+    type Input = Vec<Rucksack>;
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Rucksack Reorganization"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[Param {
+            name: "group",
+            default: "3",
+            description: "number of elves sharing a badge in part two",
+        }]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(n, line)| {
+                let mut items = line.char_indices();
+                if let Some((i, item)) = items.find(|(_, c)| !c.is_ascii_alphabetic()) {
+                    return Err(AocError::Parse(format!("unknown item type {:?}", item))
+                        .at(Location::new(n + 1, i..i + item.len_utf8(), line)));
+                }
+                let (first_part, second_part) = line.split_at(line.len() / 2);
+                Ok(Rucksack {
+                    first: HashSet::from_iter(first_part.chars()),
+                    second: HashSet::from_iter(second_part.chars()),
+                })
+            })
+            .collect()
+    }
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input, params.get("group")?)?.into())
     }
-    priority_sum
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::read_lines;
-
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example03.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 157);
-    }
 
     #[test]
-    fn test_part_two() {
-        let input = read_lines("example03.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 70);
+    fn test_group_size() {
+        let rucksacks = Day03.parse("abAb\ncdAd\nefAf\nghAh").unwrap();
+        assert_eq!(solve_part_two(&rucksacks, 2).unwrap(), 2 * 27);
+        assert_eq!(solve_part_two(&rucksacks, 4).unwrap(), 27);
+        assert!(matches!(
+            solve_part_two(&rucksacks, 3),
+            Err(AocError::Solve(_))
+        ));
+        assert!(matches!(
+            solve_part_two(&rucksacks, 0),
+            Err(AocError::Solve(_))
+        ));
     }
 }
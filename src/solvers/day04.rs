@@ -1,13 +1,46 @@
-use std::{str::FromStr, num::ParseIntError};
+use std::{fmt::Display, ops::Range, str::FromStr};
 
+use super::{Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
 
-struct Section(i32, i32);
-#[derive(Debug)]
-struct SectionParseError;
+pub struct Section(i32, i32);
 
-impl From<ParseIntError> for SectionParseError {
-    fn from(_: ParseIntError) -> Self {
-        SectionParseError
+/// What is wrong with a section assignment, with the byte range of the
+/// offending text within the parsed string.
+#[derive(Debug, PartialEq, Eq)]
+pub enum SectionParseError {
+    Pair(Range<usize>),
+    Range(Range<usize>),
+    Bound(Range<usize>),
+}
+
+impl SectionParseError {
+    fn columns(&self) -> &Range<usize> {
+        match self {
+            Self::Pair(c) | Self::Range(c) | Self::Bound(c) => c,
+        }
+    }
+
+    fn shift(self, offset: usize) -> Self {
+        let shift = |c: Range<usize>| c.start + offset..c.end + offset;
+        match self {
+            Self::Pair(c) => Self::Pair(shift(c)),
+            Self::Range(c) => Self::Range(shift(c)),
+            Self::Bound(c) => Self::Bound(shift(c)),
+        }
+    }
+}
+
+impl Display for SectionParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Pair(_) => write!(f, "expected two ranges separated by a comma"),
+            Self::Range(_) => write!(f, "expected a range like 2-4"),
+            Self::Bound(_) => write!(f, "expected a section number"),
+        }
     }
 }
 
@@ -15,15 +48,31 @@ impl FromStr for Section {
     type Err = SectionParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (low, high) = s.split_once('-').ok_or(SectionParseError)?;
-        let low_int = low.parse()?;
-        let high_int = high.parse()?;
+        let (low, high) = s
+            .split_once('-')
+            .ok_or(SectionParseError::Range(0..s.len()))?;
+        let low_int = low
+            .parse()
+            .map_err(|_| SectionParseError::Bound(0..low.len()))?;
+        let high_int = high
+            .parse()
+            .map_err(|_| SectionParseError::Bound(low.len() + 1..s.len()))?;
         Ok(Section(low_int, high_int))
     }
 }
 
-impl Section {
+fn parse_pair(line: &str) -> Result<(Section, Section), SectionParseError> {
+    let (s1, s2) = line
+        .split_once(',')
+        .ok_or(SectionParseError::Pair(0..line.len()))?;
+    let section1: Section = s1.parse()?;
+    let section2: Section = s2
+        .parse()
+        .map_err(|e: SectionParseError| e.shift(s1.len() + 1))?;
+    Ok((section1, section2))
+}
 
+impl Section {
     fn contains(&self, other: &Self) -> bool {
         self.0 <= other.0 && other.1 <= self.1
     }
@@ -31,53 +80,73 @@ impl Section {
     fn overlaps(&self, other: &Self) -> bool {
         self.1 >= other.0 && self.0 <= other.1
     }
-    
 }
 
-pub fn solve_part_one(input: &str) -> i32 {
-    let lines = input.lines();
+pub fn solve_part_one(pairs: &[(Section, Section)]) -> i32 {
     let mut overlaps = 0;
-    for line in lines {
-        let (s1, s2) = line.split_once(',').unwrap();
-        let section1: Section = s1.parse().unwrap();
-        let section2: Section = s2.parse().unwrap();
-        if section1.contains(&section2) || section2.contains(&section1) {
+    for (section1, section2) in pairs {
+        if section1.contains(section2) || section2.contains(section1) {
             overlaps += 1;
         }
     }
     overlaps
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
-    let lines = input.lines();
+pub fn solve_part_two(pairs: &[(Section, Section)]) -> i32 {
     let mut overlaps = 0;
-    for line in lines {
-        let (s1, s2) = line.split_once(',').unwrap();
-        let section1: Section = s1.parse().unwrap();
-        let section2: Section = s2.parse().unwrap();
-        if section1.overlaps(&section2) {
+    for (section1, section2) in pairs {
+        if section1.overlaps(section2) {
             overlaps += 1;
         }
     }
     overlaps
 }
 
+pub struct Day04;
+
+impl Solver for Day04 {
+    type Input = Vec<(Section, Section)>;
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Camp Cleanup"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        let lines = input.lines();
+        let mut pairs = Vec::new();
+        for (n, line) in lines.enumerate() {
+            let pair = parse_pair(line).map_err(|e| {
+                let location = Location::new(n + 1, e.columns().clone(), line);
+                AocError::from(e).at(location)
+            })?;
+            pairs.push(pair);
+        }
+        Ok(pairs)
+    }
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input).into())
+    }
+
+    fn part_two(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input).into())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::read_lines;
-
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example04.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 2);
-    }
 
     #[test]
-    fn test_part_two() {
-        let input = read_lines("example04.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 4);
+    fn test_parse_pair_errors() {
+        let error = |line| parse_pair(line).err();
+        assert_eq!(error("2-4;6-8"), Some(SectionParseError::Pair(0..7)));
+        assert_eq!(error("2-4,68"), Some(SectionParseError::Range(4..6)));
+        assert_eq!(error("2-4,6-x"), Some(SectionParseError::Bound(6..7)));
+        assert_eq!(error("a-4,6-8"), Some(SectionParseError::Bound(0..1)));
     }
 }
@@ -1,12 +1,17 @@
 use std::{
     collections::{HashMap, VecDeque},
-    num::ParseIntError,
+    fmt::Display,
+    ops::Range,
     str::FromStr,
 };
 
-use itertools::Itertools;
+use super::{Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone)]
 struct Crate(char);
 
 #[derive(Debug, PartialEq, Eq)]
@@ -16,12 +21,22 @@ enum Slot {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum ParseError {
+pub enum ParseError {
     Crate,
-    Instruction,
+    Instruction(Range<usize>),
     Line,
 }
 
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Crate => write!(f, "expected a crate like [A] or three spaces"),
+            Self::Instruction(_) => write!(f, "expected an instruction like move 1 from 2 to 3"),
+            Self::Line => write!(f, "expected a blank line after the stack numbers"),
+        }
+    }
+}
+
 impl From<Crate> for Slot {
     fn from(x: Crate) -> Self {
         Self::Crate(x)
@@ -49,23 +64,28 @@ struct Instruction {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-struct SupplyStacks {
+pub struct SupplyStacks {
     stacks: HashMap<usize, Stack>,
     instructions: Vec<Instruction>,
 }
 
 impl FromStr for SupplyStacks {
-    type Err = ParseError;
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut stacks: HashMap<usize, VecDeque<Crate>> = HashMap::new();
-        let mut lines = s.lines();
+        let mut lines = s.lines().enumerate().map(|(n, line)| (n + 1, line));
 
-        for line in lines.by_ref() {
+        for (n, line) in lines.by_ref() {
             if !line.starts_with(" 1") {
                 let mut i = 0;
                 while i * 4 + 2 < line.len() {
-                    let slot = line[i * 4..i * 4 + 3].parse::<Slot>()?;
+                    let columns = i * 4..i * 4 + 3;
+                    let slot = line
+                        .get(columns.clone())
+                        .ok_or(ParseError::Crate)
+                        .and_then(str::parse::<Slot>)
+                        .map_err(|e| AocError::from(e).at(Location::new(n, columns, line)))?;
                     if let Slot::Crate(c) = slot {
                         stacks.entry(i + 1).or_default().push_front(c);
                     }
@@ -76,13 +96,22 @@ impl FromStr for SupplyStacks {
             }
         }
         match lines.next() {
-            Some("") => Ok(()),
-            _ => Err(ParseError::Line),
+            Some((_, "")) => Ok(()),
+            Some((n, line)) => Err(AocError::from(ParseError::Line).at(Location::line(n, line))),
+            None => Err(AocError::from(ParseError::Line)),
         }?;
 
         let instructions = lines
-            .map(Instruction::from_str)
-            .collect::<Result<Vec<Instruction>, ParseError>>()?;
+            .map(|(n, line)| {
+                line.parse::<Instruction>().map_err(|e| {
+                    let location = match &e {
+                        ParseError::Instruction(columns) => Location::new(n, columns.clone(), line),
+                        _ => Location::line(n, line),
+                    };
+                    AocError::from(e).at(location)
+                })
+            })
+            .collect::<Result<Vec<Instruction>, AocError>>()?;
 
         Ok(Self {
             stacks,
@@ -112,13 +141,7 @@ impl FromStr for Crate {
         } else {
             Err(Self::Err::Crate)
         }?;
-        Ok(Self(n.unwrap()))
-    }
-}
-
-impl From<ParseIntError> for ParseError {
-    fn from(_: ParseIntError) -> Self {
-        Self::Instruction
+        Ok(Self(n.ok_or(Self::Err::Crate)?))
     }
 }
 
@@ -127,99 +150,131 @@ impl FromStr for Instruction {
 
     fn from_str(x: &str) -> Result<Self, Self::Err> {
         let mut s = x;
+        // error pointing at the next word of the remaining `s`, or at all of it
+        let word = |s: &str| {
+            let start = x.len() - s.len();
+            ParseError::Instruction(start..s.find(' ').map_or(x.len(), |i| start + i))
+        };
+        let rest = |s: &str| ParseError::Instruction(x.len() - s.len()..x.len());
 
         if s.starts_with("move ") {
             Ok(())
         } else {
-            Err(Self::Err::Instruction)
+            Err(word(s))
         }?;
         s = &s[5..];
 
-        let space = s.find(' ').ok_or(Self::Err::Instruction)?;
-        let amount: usize = s[..space].parse()?;
+        let space = s.find(' ').ok_or_else(|| rest(s))?;
+        let amount: usize = s[..space].parse().map_err(|_| word(s))?;
         s = &s[space + 1..];
 
         if let Some(0) = s.find("from ") {
             Ok(())
         } else {
-            Err(Self::Err::Instruction)
+            Err(word(s))
         }?;
         s = &s[5..];
 
-        let space = s.find(' ').ok_or(Self::Err::Instruction)?;
-        let from: usize = s[..space].parse()?;
+        let space = s.find(' ').ok_or_else(|| rest(s))?;
+        let from: usize = s[..space].parse().map_err(|_| word(s))?;
         s = &s[space + 1..];
 
         if let Some(0) = s.find("to ") {
             Ok(())
         } else {
-            Err(Self::Err::Instruction)
+            Err(word(s))
         }?;
         s = &s[3..];
 
-        let to: usize = s.parse()?;
+        let to: usize = s.parse().map_err(|_| rest(s))?;
         Ok(Self { amount, from, to })
     }
 }
 
+fn stack<'a>(stacks: &'a mut HashMap<usize, Stack>, i: &usize) -> Result<&'a mut Stack, AocError> {
+    stacks
+        .get_mut(i)
+        .ok_or_else(|| AocError::Solve(format!("there is no stack {}", i)))
+}
+
+fn tops(stacks: &HashMap<usize, Stack>) -> Result<String, AocError> {
+    (1..=stacks.len())
+        .map(|i| stacks.get(&i).and_then(|v| v.back()).map(|c| c.0))
+        .collect::<Option<String>>()
+        .ok_or_else(|| AocError::Solve("a stack ended up empty".to_string()))
+}
+
 impl SupplyStacks {
-    fn simulate(&mut self) -> String {
-        for instr in self.instructions.drain(..) {
-            let from = self.stacks.get_mut(&instr.from).unwrap();
-            let s = from.split_off(from.len() - instr.amount);
-            let stack = self.stacks.get_mut(&instr.to).unwrap();
-            stack.extend(s.into_iter().rev());
+    fn take(stacks: &mut HashMap<usize, Stack>, instr: &Instruction) -> Result<Stack, AocError> {
+        let from = stack(stacks, &instr.from)?;
+        let remaining = from.len().checked_sub(instr.amount).ok_or_else(|| {
+            AocError::Solve(format!(
+                "cannot move {} crates from stack {} holding {}",
+                instr.amount,
+                instr.from,
+                from.len()
+            ))
+        })?;
+        Ok(from.split_off(remaining))
+    }
+
+    fn simulate(&self) -> Result<String, AocError> {
+        let mut stacks = self.stacks.clone();
+        for instr in &self.instructions {
+            let s = Self::take(&mut stacks, instr)?;
+            stack(&mut stacks, &instr.to)?.extend(s.into_iter().rev());
         }
-        (1..=self.stacks.len())
-            .map(|i| self.stacks.get(&i))
-            .map(|m| m.and_then(|v| v.back()))
-            .map(|c| c.unwrap().0)
-            .join("")
+        tops(&stacks)
     }
 
-    fn simulate2(&mut self) -> String {
-        for instr in self.instructions.drain(..) {
-            let from = self.stacks.get_mut(&instr.from).unwrap();
-            let s = from.split_off(from.len() - instr.amount);
-            let stack = self.stacks.get_mut(&instr.to).unwrap();
-            stack.extend(s);
+    fn simulate2(&self) -> Result<String, AocError> {
+        let mut stacks = self.stacks.clone();
+        for instr in &self.instructions {
+            let s = Self::take(&mut stacks, instr)?;
+            stack(&mut stacks, &instr.to)?.extend(s);
         }
-        (1..=self.stacks.len())
-            .map(|i| self.stacks.get(&i))
-            .map(|m| m.and_then(|v| v.back()))
-            .map(|c| c.unwrap().0)
-            .join("")
+        tops(&stacks)
     }
 }
 
-pub fn solve_part_one(input: &str) -> String {
-    let mut supply_stacks = SupplyStacks::from_str(input).unwrap();
+pub fn solve_part_one(supply_stacks: &SupplyStacks) -> Result<String, AocError> {
     supply_stacks.simulate()
 }
 
-pub fn solve_part_two(input: &str) -> String {
-    let mut supply_stacks = SupplyStacks::from_str(input).unwrap();
+pub fn solve_part_two(supply_stacks: &SupplyStacks) -> Result<String, AocError> {
     supply_stacks.simulate2()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::read_lines;
+pub struct Day05;
 
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example05.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, "CMZ");
+impl Solver for Day05 {
+    type Input = SupplyStacks;
+
+    fn day(&self) -> u8 {
+        5
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = read_lines("example05.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, "MCD");
+    fn title(&self) -> &'static str {
+        "Supply Stacks"
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        SupplyStacks::from_str(input)
+    }
+
+    fn part_one(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input, _params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input)?.into())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixtures::Fixture;
 
     #[test]
     fn test_instruction_from_str_1() {
@@ -253,7 +308,7 @@ mod tests {
     fn test_instruction_from_str_3() {
         let input = "move 1 from 1 to 2 ";
         let res = input.parse::<Instruction>();
-        assert_eq!(res, Err(ParseError::Instruction),);
+        assert_eq!(res, Err(ParseError::Instruction(17..19)),);
     }
 
     #[test]
@@ -279,12 +334,12 @@ mod tests {
 
     #[test]
     fn test_seacks_from_str_1() {
-        let input = read_lines("example05.in").unwrap();
+        let input = Fixture::load("example05.toml").unwrap().input;
         let res = input.parse::<SupplyStacks>();
 
         let stacks: Vec<(usize, VecDeque<_>)> = vec![
-            (1, vec![Crate('N'), Crate('Z')].into()),
-            (2, vec![Crate('D'), Crate('C'), Crate('M')].into()),
+            (1, vec![Crate('Z'), Crate('N')].into()),
+            (2, vec![Crate('M'), Crate('C'), Crate('D')].into()),
             (3, vec![Crate('P')].into()),
         ];
         let supply_stacks = SupplyStacks {
@@ -312,6 +367,43 @@ mod tests {
                 },
             ],
         };
-        assert_eq!(res, Ok(supply_stacks));
+        assert_eq!(res.unwrap(), supply_stacks);
+    }
+
+    #[test]
+    fn test_too_many_crates() {
+        let input = Day05.parse("[A]\n 1 \n\nmove 2 from 1 to 1").unwrap();
+        assert!(matches!(solve_part_one(&input), Err(AocError::Solve(_))));
+    }
+
+    #[test]
+    fn test_instruction_from_str_4() {
+        let input = "move 1 form 1 to 2";
+        let res = input.parse::<Instruction>();
+        assert_eq!(res, Err(ParseError::Instruction(7..11)));
+    }
+
+    #[test]
+    fn test_stacks_error_location() {
+        let input = "[A] [B]\n[C] (D)\n 1   2 \n\nmove 1 from 1 to 2";
+        let res = input.parse::<SupplyStacks>();
+        match res {
+            Err(AocError::At(location, _)) => {
+                assert_eq!(location, Location::new(2, 4..7, "[C] (D)"))
+            }
+            _ => panic!("expected a located error"),
+        }
+    }
+
+    #[test]
+    fn test_stacks_not_on_char_boundary() {
+        let res = "abé\n 1 \n\n".parse::<SupplyStacks>();
+        match res {
+            Err(AocError::At(location, e)) => {
+                assert_eq!(location, Location::new(1, 0..3, "abé"));
+                assert!(matches!(*e, AocError::SupplyStacks(ParseError::Crate)));
+            }
+            _ => panic!("expected a located error"),
+        }
     }
 }
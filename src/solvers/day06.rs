@@ -2,23 +2,28 @@ use std::{collections::VecDeque, iter::Enumerate};
 
 use unicode_segmentation::{Graphemes, UnicodeSegmentation};
 
-struct InstructionStream<'a, const BUCKET_SIZE: usize> {
+use super::{Param, Params, Solver};
+use crate::{error::AocError, Answer};
+
+struct InstructionStream<'a> {
     iter: Enumerate<Graphemes<'a>>,
     bucket: VecDeque<&'a str>,
+    bucket_size: usize,
     found: bool,
 }
 
-impl<'a, const BUCKET_SIZE: usize> InstructionStream<'a, BUCKET_SIZE> {
-    fn new(data: &'a str) -> Self {
+impl<'a> InstructionStream<'a> {
+    fn new(data: &'a str, bucket_size: usize) -> Self {
         Self {
             iter: data.graphemes(true).enumerate(),
             bucket: VecDeque::new(),
+            bucket_size,
             found: false,
         }
     }
 }
 
-impl<'a, const BUCKET_SIZE: usize> Iterator for InstructionStream<'a, BUCKET_SIZE> {
+impl<'a> Iterator for InstructionStream<'a> {
     type Item = Option<i32>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -39,7 +44,7 @@ impl<'a, const BUCKET_SIZE: usize> Iterator for InstructionStream<'a, BUCKET_SIZ
                 self.bucket.push_back(x);
             }
 
-            if self.bucket.len() < BUCKET_SIZE {
+            if self.bucket.len() < self.bucket_size {
                 Some(None)
             } else {
                 self.found = true;
@@ -51,68 +56,79 @@ impl<'a, const BUCKET_SIZE: usize> Iterator for InstructionStream<'a, BUCKET_SIZ
     }
 }
 
-pub fn solve_part_one(input: &str) -> i32 {
-    let instructions: InstructionStream<4> = InstructionStream::new(input.lines().next().unwrap());
+/// Number of characters read once the last `marker_size` are all different.
+pub fn first_marker(datastream: &str, marker_size: usize) -> Result<i32, AocError> {
+    if marker_size == 0 {
+        return Err(AocError::Param(
+            "a marker is at least 1 character long".to_string(),
+        ));
+    }
+    let instructions = InstructionStream::new(datastream, marker_size);
     for x in instructions {
         match x {
-            Some(c) => return c,
+            Some(c) => return Ok(c),
             None => continue,
         }
     }
-    0
+    Err(AocError::Solve(format!(
+        "no {} distinct characters in a row",
+        marker_size
+    )))
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
-    let instructions: InstructionStream<14> = InstructionStream::new(input.lines().next().unwrap());
-    for x in instructions {
-        match x {
-            Some(c) => return c,
-            None => continue,
-        }
+pub struct Day06;
+
+impl Solver for Day06 {
+    type Input = String;
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Tuning Trouble"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param {
+                name: "packet",
+                default: "4",
+                description: "distinct characters in a start-of-packet marker",
+            },
+            Param {
+                name: "message",
+                default: "14",
+                description: "distinct characters in a start-of-message marker",
+            },
+        ]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        input
+            .lines()
+            .next()
+            .map(str::to_string)
+            .ok_or_else(|| AocError::Parse("the datastream is empty".to_string()))
+    }
+
+    fn part_one(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(first_marker(input, params.get("packet")?)?.into())
+    }
+
+    fn part_two(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(first_marker(input, params.get("message")?)?.into())
     }
-    0
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::read_lines;
-
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example06-1.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 7);
-        let input = read_lines("example06-2.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 5);
-        let input = read_lines("example06-3.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 6);
-        let input = read_lines("example06-4.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 10);
-        let input = read_lines("example06-5.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 11);
-    }
 
     #[test]
-    fn test_part_two() {
-        let input = read_lines("example06-1.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 19);
-        let input = read_lines("example06-2.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 23);
-        let input = read_lines("example06-3.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 23);
-        let input = read_lines("example06-4.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 29);
-        let input = read_lines("example06-5.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 26);
+    fn test_no_marker() {
+        assert!(matches!(first_marker("aaaa", 4), Err(AocError::Solve(_))));
+        assert_eq!(first_marker("abcd", 4).unwrap(), 4);
+        assert!(matches!(first_marker("abcd", 0), Err(AocError::Param(_))));
     }
 }
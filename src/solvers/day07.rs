@@ -1,66 +1,538 @@
-use std::str::FromStr;
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Display,
+    str::FromStr,
+};
 
-#[derive(PartialEq, Eq)]
-enum Command<'a> {
-    Cd(&'a str),
+pub mod generate;
+mod render;
+pub mod shell;
+
+use super::{Param, Params, Solver};
+use crate::{
+    error::{AocError, Location},
+    Answer,
+};
+
+#[derive(Debug, PartialEq, Eq)]
+enum Command {
+    Cd(String),
     Ls,
 }
-enum ParsingError {
+
+/// A line of `ls` output.
+#[derive(Debug, PartialEq, Eq)]
+enum Entry {
+    Dir(String),
+    File(usize, String),
+}
+
+/// What is wrong with a transcript line, either on its own or because it
+/// contradicts what the transcript showed before.
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParsingError {
     Command,
+    Entry,
+    /// `ls` output while no `ls` is running.
+    Output,
+    /// `cd` to a path through a directory no listing has shown.
+    UnknownDirectory(String),
+    /// A file listed again with a different size.
+    Size {
+        name: String,
+        listed: usize,
+        now: usize,
+    },
+    /// A name listed both as a file and as a directory.
+    Kind(String),
+    /// A directory listed again without an entry it had before.
+    Missing(String),
+    /// A directory listed again with an entry it didn't have before.
+    New(String),
+}
+
+impl Display for ParsingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Command => write!(f, "expected $ cd PATH or $ ls"),
+            Self::Entry => write!(f, "expected dir NAME or SIZE NAME"),
+            Self::Output => write!(f, "output while no ls is running"),
+            Self::UnknownDirectory(path) => {
+                write!(
+                    f,
+                    "cd {} goes through a directory no listing has shown",
+                    path
+                )
+            }
+            Self::Size { name, listed, now } => {
+                write!(f, "{} listed with size {}, earlier {}", name, now, listed)
+            }
+            Self::Kind(name) => write!(f, "{} listed both as a file and as a directory", name),
+            Self::Missing(name) => write!(f, "{} is missing from a repeated listing", name),
+            Self::New(name) => write!(f, "{} is new in a repeated listing", name),
+        }
+    }
+}
+
+impl FromStr for Command {
+    type Err = ParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let x = s.strip_prefix("$ ").ok_or(ParsingError::Command)?;
+        match x.split_once(' ') {
+            None if x == "ls" => Ok(Command::Ls),
+            Some(("cd", dir)) if !dir.is_empty() => Ok(Command::Cd(dir.to_string())),
+            _ => Err(ParsingError::Command),
+        }
+    }
 }
 
-impl<'a> FromStr for Command<'a> {
+impl FromStr for Entry {
     type Err = ParsingError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut x = s;
-        if !x.starts_with("$ ") {
-            return Err(ParsingError::Command);
+        match s.split_once(' ') {
+            Some((_, name)) if name.is_empty() || name.contains('/') => Err(ParsingError::Entry),
+            Some(("dir", name)) => Ok(Entry::Dir(name.to_string())),
+            Some((size, name)) => size
+                .parse()
+                .map(|size| Entry::File(size, name.to_string()))
+                .map_err(|_| ParsingError::Entry),
+            None => Err(ParsingError::Entry),
+        }
+    }
+}
+
+impl Entry {
+    fn name(&self) -> &str {
+        match self {
+            Entry::Dir(name) | Entry::File(_, name) => name,
+        }
+    }
+}
+
+enum Node {
+    Dir(usize),
+    File(usize),
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct Directory {
+    name: String,
+    parent: Option<usize>,
+    listed: bool,
+    dirs: BTreeMap<String, usize>,
+    files: BTreeMap<String, usize>,
+}
+
+/// The directory tree seen in the transcript. Directories are stored in the
+/// order they were discovered, so a parent always comes before its children;
+/// the root is the first one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Filesystem {
+    dirs: Vec<Directory>,
+}
+
+const ROOT: usize = 0;
+
+/// A running `ls`: its line and the names it listed so far.
+type Listing = (Location, BTreeSet<String>);
+
+impl Filesystem {
+    fn new() -> Self {
+        Self {
+            dirs: vec![Directory {
+                name: "/".to_string(),
+                ..Directory::default()
+            }],
+        }
+    }
+
+    fn subdirectory(&mut self, dir: usize, name: &str) -> usize {
+        if let Some(&child) = self.dirs[dir].dirs.get(name) {
+            return child;
+        }
+        let child = self.dirs.len();
+        self.dirs.push(Directory {
+            name: name.to_string(),
+            parent: Some(dir),
+            ..Directory::default()
+        });
+        self.dirs[dir].dirs.insert(name.to_string(), child);
+        child
+    }
+
+    fn entries(&self, dir: usize) -> BTreeSet<&str> {
+        let directory = &self.dirs[dir];
+        directory
+            .dirs
+            .keys()
+            .chain(directory.files.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Subdirectories and files of `dir`, sorted by name like the puzzle
+    /// lists them.
+    fn children(&self, dir: usize) -> Vec<(&str, Node)> {
+        let directory = &self.dirs[dir];
+        let mut children: Vec<(&str, Node)> = directory
+            .dirs
+            .iter()
+            .map(|(name, &child)| (name.as_str(), Node::Dir(child)))
+            .chain(
+                directory
+                    .files
+                    .iter()
+                    .map(|(name, &size)| (name.as_str(), Node::File(size))),
+            )
+            .collect();
+        children.sort_by_key(|(name, _)| *name);
+        children
+    }
+
+    /// `dir` and every directory below it, parents first.
+    fn descendants(&self, dir: usize) -> Vec<usize> {
+        let mut dirs = vec![dir];
+        let mut i = 0;
+        while let Some(&dir) = dirs.get(i) {
+            dirs.extend(self.dirs[dir].dirs.values());
+            i += 1;
+        }
+        dirs
+    }
+
+    fn path(&self, dir: usize) -> String {
+        let mut names = Vec::new();
+        let mut current = dir;
+        while let Some(parent) = self.dirs[current].parent {
+            names.push(self.dirs[current].name.as_str());
+            current = parent;
+        }
+        names.reverse();
+        format!("/{}", names.join("/"))
+    }
+
+    /// Follows `path` from `cwd` like a shell would: `/` starts at the root,
+    /// `..` at the root stays there and every other component must be a
+    /// directory that has been listed.
+    fn resolve(&self, cwd: usize, path: &str) -> Result<usize, ParsingError> {
+        let mut dir = if path.starts_with('/') { ROOT } else { cwd };
+        for component in path.split('/').filter(|c| !c.is_empty() && *c != ".") {
+            dir = match component {
+                ".." => self.dirs[dir].parent.unwrap_or(ROOT),
+                name => *self.dirs[dir]
+                    .dirs
+                    .get(name)
+                    .ok_or_else(|| ParsingError::UnknownDirectory(path.to_string()))?,
+            };
+        }
+        Ok(dir)
+    }
+
+    /// Adds a listed entry to `dir`. A directory listed before already has
+    /// all its entries, so they must match.
+    fn add(&mut self, dir: usize, entry: Entry) -> Result<(), ParsingError> {
+        let directory = &self.dirs[dir];
+        let name = entry.name();
+        let is_file = directory.files.contains_key(name);
+        let is_dir = directory.dirs.contains_key(name);
+        if directory.listed && !is_file && !is_dir {
+            return Err(ParsingError::New(name.to_string()));
+        }
+        match entry {
+            Entry::Dir(name) if is_file => Err(ParsingError::Kind(name)),
+            Entry::Dir(name) => {
+                self.subdirectory(dir, &name);
+                Ok(())
+            }
+            Entry::File(_, name) if is_dir => Err(ParsingError::Kind(name)),
+            Entry::File(size, name) => match self.dirs[dir].files.insert(name.clone(), size) {
+                Some(listed) if listed != size => Err(ParsingError::Size {
+                    name,
+                    listed,
+                    now: size,
+                }),
+                _ => Ok(()),
+            },
+        }
+    }
+
+    /// Marks `dir` as listed once an `ls` of it ends, checking that a
+    /// repeated listing didn't leave anything out.
+    fn finish_listing(&mut self, dir: usize, listing: Option<Listing>) -> Result<(), AocError> {
+        let (location, listed) = match listing {
+            Some(listing) => listing,
+            None => return Ok(()),
         };
-        x = &x[2..];
-        match x[..2] {
-            "ls" if x.len() == 2 => Command::Ls,
-            "cd" => 
+        if self.dirs[dir].listed {
+            if let Some(missing) = self.entries(dir).into_iter().find(|e| !listed.contains(*e)) {
+                return Err(AocError::from(ParsingError::Missing(missing.to_string())).at(location));
+            }
         }
+        self.dirs[dir].listed = true;
+        Ok(())
+    }
+
+    /// Total size of every directory, including its subdirectories, indexed
+    /// like `dirs`.
+    fn sizes(&self) -> Vec<usize> {
+        let mut sizes: Vec<usize> = self.dirs.iter().map(|d| d.files.values().sum()).collect();
+        for (dir, directory) in self.dirs.iter().enumerate().rev() {
+            if let Some(parent) = directory.parent {
+                sizes[parent] += sizes[dir];
+            }
+        }
+        sizes
     }
 }
 
+impl FromStr for Filesystem {
+    type Err = AocError;
 
-pub fn solve_part_one(input: &str) -> i32 {
-    0
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut filesystem = Filesystem::new();
+        let mut cwd = ROOT;
+        let mut listing: Option<Listing> = None;
+        for (n, line) in s.lines().enumerate().map(|(n, line)| (n + 1, line)) {
+            let error = |e: ParsingError| AocError::from(e).at(Location::line(n, line));
+            if line.starts_with('$') {
+                filesystem.finish_listing(cwd, listing.take())?;
+                match line.parse().map_err(error)? {
+                    Command::Cd(path) => {
+                        cwd = filesystem.resolve(cwd, &path).map_err(|e| {
+                            AocError::from(e).at(Location::within(n, line, &line[5..]))
+                        })?;
+                    }
+                    Command::Ls => listing = Some((Location::line(n, line), BTreeSet::new())),
+                }
+            } else {
+                let listed = match &mut listing {
+                    Some((_, listed)) => listed,
+                    None => return Err(error(ParsingError::Output)),
+                };
+                let entry: Entry = line.parse().map_err(error)?;
+                let name = entry.name().to_string();
+                filesystem.add(cwd, entry).map_err(|e| {
+                    AocError::from(e).at(Location::new(
+                        n,
+                        line.len() - name.len()..line.len(),
+                        line,
+                    ))
+                })?;
+                listed.insert(name);
+            }
+        }
+        filesystem.finish_listing(cwd, listing)?;
+        Ok(filesystem)
+    }
 }
 
-pub fn solve_part_two(input: &str) -> i32 {
-    0
+pub fn solve_part_one(filesystem: &Filesystem, limit: usize) -> usize {
+    filesystem.sizes().into_iter().filter(|&s| s <= limit).sum()
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::read_lines;
+/// Size of the smallest directory whose removal leaves `needed` free on a
+/// disk of `capacity`.
+pub fn solve_part_two(
+    filesystem: &Filesystem,
+    capacity: usize,
+    needed: usize,
+) -> Result<usize, AocError> {
+    let sizes = filesystem.sizes();
+    let free = capacity.checked_sub(sizes[ROOT]).ok_or_else(|| {
+        AocError::Solve(format!(
+            "the files take {} but the disk only holds {}",
+            sizes[ROOT], capacity
+        ))
+    })?;
+    let missing = needed.saturating_sub(free);
+    if missing == 0 {
+        return Err(AocError::Solve(format!(
+            "{} is already free, no directory needs to be deleted",
+            free
+        )));
+    }
+    sizes
+        .into_iter()
+        .filter(|&s| s >= missing)
+        .min()
+        .ok_or_else(|| AocError::Solve(format!("cannot free {} on the disk", needed)))
+}
 
-    #[test]
-    fn test_part_one() {
-        let input = read_lines("example07.in").unwrap();
-        let res = solve_part_one(&input);
-        assert_eq!(res, 95437);
+pub struct Day07;
+
+impl Solver for Day07 {
+    type Input = Filesystem;
+
+    fn day(&self) -> u8 {
+        7
     }
 
-    #[test]
-    fn test_part_two() {
-        let input = read_lines("example07.in").unwrap();
-        let res = solve_part_two(&input);
-        assert_eq!(res, 19);
+    fn title(&self) -> &'static str {
+        "No Space Left On Device"
+    }
+
+    fn params(&self) -> &'static [Param] {
+        &[
+            Param {
+                name: "limit",
+                default: "100000",
+                description: "largest directory counted in part one",
+            },
+            Param {
+                name: "capacity",
+                default: "70000000",
+                description: "total disk space",
+            },
+            Param {
+                name: "needed",
+                default: "30000000",
+                description: "free space needed for the update",
+            },
+        ]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError> {
+        input.parse()
+    }
+
+    fn part_one(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_one(input, params.get("limit")?).into())
     }
 
+    fn part_two(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError> {
+        Ok(solve_part_two(input, params.get("capacity")?, params.get("needed")?)?.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    pub(super) const EXAMPLE: &str =
+        "$ cd /\n$ ls\ndir a\n14848514 b.txt\n8504156 c.dat\ndir d\n$ cd a\n\
+        $ ls\ndir e\n29116 f\n2557 g\n62596 h.lst\n$ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n\
+        $ cd d\n$ ls\n4060174 j\n8033020 d.log\n5626152 d.ext\n7214296 k\n";
+
     #[test]
-    fn test_Command_paring() {
+    fn test_command_parsing() {
         let input = "$ cd /";
-        let output = Command::Cd("/");
+        let output = Command::Cd("/".to_string());
         assert_eq!(input.parse(), Ok(output));
 
         let input = "$ ls";
         let output = Command::Ls;
         assert_eq!(input.parse(), Ok(output));
+
+        assert_eq!("$ ls -l".parse::<Command>(), Err(ParsingError::Command));
+        assert_eq!("$ cd".parse::<Command>(), Err(ParsingError::Command));
+        assert_eq!("cd /".parse::<Command>(), Err(ParsingError::Command));
+    }
+
+    #[test]
+    fn test_entry_parsing() {
+        assert_eq!("dir a".parse(), Ok(Entry::Dir("a".to_string())));
+        assert_eq!(
+            "14848514 b.txt".parse(),
+            Ok(Entry::File(14848514, "b.txt".to_string()))
+        );
+        assert_eq!("big b.txt".parse::<Entry>(), Err(ParsingError::Entry));
+        assert_eq!("584".parse::<Entry>(), Err(ParsingError::Entry));
+    }
+
+    #[test]
+    fn test_sizes() {
+        let filesystem: Filesystem = EXAMPLE.parse().unwrap();
+        let mut sizes = filesystem.sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![584, 94853, 24933642, 48381165]);
+    }
+
+    #[test]
+    fn test_solve() {
+        let filesystem: Filesystem = EXAMPLE.parse().unwrap();
+        assert_eq!(solve_part_one(&filesystem, 100000), 95437);
+        assert_eq!(
+            solve_part_two(&filesystem, 70000000, 30000000).unwrap(),
+            24933642
+        );
+        assert!(solve_part_two(&filesystem, 40000000, 30000000).is_err());
+        assert!(matches!(
+            solve_part_two(&filesystem, 100000000, 30000000),
+            Err(AocError::Solve(_))
+        ));
+    }
+
+    fn error(transcript: &str) -> (ParsingError, usize) {
+        match transcript.parse::<Filesystem>() {
+            Err(AocError::At(location, e)) => match *e {
+                AocError::Transcript(e) => (e, location.line),
+                e => panic!("unexpected error {}", e),
+            },
+            other => panic!("expected a transcript error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_paths() {
+        let filesystem: Filesystem = "$ ls\ndir a\n$ cd a\n$ ls\ndir b\n$ cd b\n$ ls\n1 x\n"
+            .parse()
+            .unwrap();
+        let b = filesystem.resolve(ROOT, "a/b").unwrap();
+        assert_eq!(filesystem.resolve(ROOT, "/a/./b/").unwrap(), b);
+        assert_eq!(filesystem.resolve(b, "../../a/b").unwrap(), b);
+        assert_eq!(filesystem.resolve(b, "/").unwrap(), ROOT);
+        assert_eq!(filesystem.resolve(ROOT, "..").unwrap(), ROOT);
+        assert_eq!(
+            filesystem.resolve(b, "../c"),
+            Err(ParsingError::UnknownDirectory("../c".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_repeated_ls() {
+        let transcript = "$ cd /\n$ ls\ndir a\n10 b\n$ cd ..\n$ ls\n10 b\ndir a\n";
+        let filesystem: Filesystem = transcript.parse().unwrap();
+        assert_eq!(filesystem.sizes(), vec![10, 0]);
+    }
+
+    #[test]
+    fn test_contradictions() {
+        assert_eq!(
+            error("$ ls\n10 b\n$ ls\n12 b\n"),
+            (
+                ParsingError::Size {
+                    name: "b".to_string(),
+                    listed: 10,
+                    now: 12,
+                },
+                4
+            )
+        );
+        assert_eq!(
+            error("$ ls\n10 b\n10 b\ndir b\n"),
+            (ParsingError::Kind("b".to_string()), 4)
+        );
+        assert_eq!(
+            error("$ ls\n10 b\ndir a\n$ ls\n10 b\n$ cd a\n"),
+            (ParsingError::Missing("a".to_string()), 4)
+        );
+        assert_eq!(
+            error("$ ls\n10 b\n$ ls\n10 b\n1 c\n"),
+            (ParsingError::New("c".to_string()), 5)
+        );
+        assert_eq!(
+            error("$ cd /\n$ cd a\n"),
+            (ParsingError::UnknownDirectory("a".to_string()), 2)
+        );
+        assert_eq!(error("$ cd /\ndir a\n"), (ParsingError::Output, 2));
+        assert_eq!(error("$ ls\ndir a/b\n"), (ParsingError::Entry, 2));
+    }
+
+    #[test]
+    fn test_parse_error_location() {
+        let error = "$ cd /\n$ ls\n12x a\n".parse::<Filesystem>().unwrap_err();
+        assert!(error.to_string().contains("--> line 3, column 1"));
     }
 }
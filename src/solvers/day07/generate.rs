@@ -0,0 +1,213 @@
+//! Transcripts of real directory trees, for stress testing the parser.
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use super::{Filesystem, ROOT};
+use crate::error::AocError;
+
+/// splitmix64, enough to shuffle reproducibly without a dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = (self.next() % (i as u64 + 1)) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+enum Item {
+    Dir(String, PathBuf),
+    File(String, usize),
+}
+
+/// Subdirectories and regular files of `dir` sorted by name. Symbolic links
+/// and other special files are left out.
+fn read_dir(dir: &Path) -> Result<Vec<Item>, AocError> {
+    let error = |e| AocError::Read(dir.display().to_string(), e);
+    let mut items = Vec::new();
+    for entry in fs::read_dir(dir).map_err(error)? {
+        let entry = entry.map_err(error)?;
+        let path = entry.path();
+        let name = entry
+            .file_name()
+            .into_string()
+            .ok()
+            .filter(|n| !n.contains('\n'))
+            .ok_or_else(|| {
+                AocError::Parse(format!(
+                    "{} cannot be written in a transcript",
+                    path.display()
+                ))
+            })?;
+        let file_type = entry.file_type().map_err(error)?;
+        if file_type.is_dir() {
+            items.push(Item::Dir(name, path));
+        } else if file_type.is_file() {
+            let size = entry.metadata().map_err(error)?.len() as usize;
+            items.push(Item::File(name, size));
+        }
+    }
+    items.sort_by(|a, b| name(a).cmp(name(b)));
+    Ok(items)
+}
+
+fn name(item: &Item) -> &str {
+    match item {
+        Item::Dir(name, _) | Item::File(name, _) => name,
+    }
+}
+
+/// A transcript listing every directory below `root` once. With a seed the
+/// entries of each listing and the order directories are visited in are
+/// shuffled.
+pub fn transcript(root: &Path, seed: Option<u64>) -> Result<String, AocError> {
+    fn walk(dir: &Path, rng: &mut Option<Rng>, out: &mut String) -> Result<(), AocError> {
+        let mut items = read_dir(dir)?;
+        if let Some(rng) = rng {
+            rng.shuffle(&mut items);
+        }
+        out.push_str("$ ls\n");
+        for item in &items {
+            match item {
+                Item::Dir(name, _) => out.push_str(&format!("dir {}\n", name)),
+                Item::File(name, size) => out.push_str(&format!("{} {}\n", size, name)),
+            }
+        }
+        let mut dirs: Vec<(&String, &PathBuf)> = items
+            .iter()
+            .filter_map(|item| match item {
+                Item::Dir(name, path) => Some((name, path)),
+                Item::File(..) => None,
+            })
+            .collect();
+        if let Some(rng) = rng {
+            rng.shuffle(&mut dirs);
+        }
+        for (name, path) in dirs {
+            out.push_str(&format!("$ cd {}\n", name));
+            walk(path, rng, out)?;
+            out.push_str("$ cd ..\n");
+        }
+        Ok(())
+    }
+    let mut out = String::from("$ cd /\n");
+    walk(root, &mut seed.map(Rng), &mut out)?;
+    Ok(out)
+}
+
+/// Compares every file and directory size of `filesystem` with the tree at
+/// `root` on disk, returning how many directories were checked.
+pub fn check(root: &Path, filesystem: &Filesystem) -> Result<usize, AocError> {
+    fn walk(
+        path: &Path,
+        filesystem: &Filesystem,
+        sizes: &[usize],
+        dir: usize,
+    ) -> Result<(usize, usize), AocError> {
+        let mismatch =
+            |what: String| AocError::Solve(format!("{}: {}", filesystem.path(dir), what));
+        let mut total = 0;
+        let mut checked = 1;
+        for item in read_dir(path)? {
+            match item {
+                Item::Dir(name, path) => {
+                    let child = *filesystem.dirs[dir]
+                        .dirs
+                        .get(&name)
+                        .ok_or_else(|| mismatch(format!("directory {} is missing", name)))?;
+                    let (size, count) = walk(&path, filesystem, sizes, child)?;
+                    total += size;
+                    checked += count;
+                }
+                Item::File(name, size) => {
+                    match filesystem.dirs[dir].files.get(&name) {
+                        Some(&listed) if listed == size => {}
+                        Some(&listed) => {
+                            return Err(mismatch(format!(
+                                "{} is {} on disk but {} in the transcript",
+                                name, size, listed
+                            )))
+                        }
+                        None => return Err(mismatch(format!("file {} is missing", name))),
+                    }
+                    total += size;
+                }
+            }
+        }
+        if total != sizes[dir] {
+            return Err(mismatch(format!(
+                "takes {} on disk but {} in the transcript",
+                total, sizes[dir]
+            )));
+        }
+        Ok((total, checked))
+    }
+    let (_, checked) = walk(root, filesystem, &filesystem.sizes(), ROOT)?;
+    Ok(checked)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A fresh directory tree under the system temp directory.
+    fn sample_tree(name: &str) -> PathBuf {
+        let root = std::env::temp_dir().join(format!("aoc22-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        for dir in ["a/e", "d", "empty"] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        for (file, size) in [("b.txt", 1484), ("a/f", 29), ("a/e/i", 584), ("d/j j", 406)] {
+            fs::write(root.join(file), vec![b'x'; size]).unwrap();
+        }
+        root
+    }
+
+    #[test]
+    fn test_transcript() {
+        let root = sample_tree("transcript");
+        let transcript = transcript(&root, None).unwrap();
+        assert_eq!(
+            transcript,
+            "$ cd /\n$ ls\ndir a\n1484 b.txt\ndir d\ndir empty\n$ cd a\n$ ls\ndir e\n29 f\n\
+             $ cd e\n$ ls\n584 i\n$ cd ..\n$ cd ..\n$ cd d\n$ ls\n406 j j\n$ cd ..\n\
+             $ cd empty\n$ ls\n$ cd ..\n"
+        );
+        let filesystem: Filesystem = transcript.parse().unwrap();
+        assert_eq!(filesystem.sizes()[ROOT], 1484 + 29 + 584 + 406);
+        assert_eq!(check(&root, &filesystem).unwrap(), 5);
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_shuffled() {
+        let root = sample_tree("shuffled");
+        let sorted = transcript(&root, None).unwrap();
+        let shuffled: Vec<String> = (0..8)
+            .map(|seed| transcript(&root, Some(seed)).unwrap())
+            .collect();
+        assert_eq!(transcript(&root, Some(3)).unwrap(), shuffled[3]);
+        assert!(shuffled.iter().any(|t| *t != sorted));
+        for transcript in &shuffled {
+            let filesystem: Filesystem = transcript.parse().unwrap();
+            assert_eq!(check(&root, &filesystem).unwrap(), 5);
+        }
+        fs::write(root.join("d/j j"), "changed").unwrap();
+        let filesystem: Filesystem = sorted.parse().unwrap();
+        assert!(check(&root, &filesystem).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+}
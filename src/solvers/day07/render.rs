@@ -0,0 +1,242 @@
+//! Views of the reconstructed filesystem, for inspecting odd inputs.
+
+use serde_json::{json, Value};
+
+use super::{Filesystem, Node, ROOT};
+
+impl Filesystem {
+    /// The tree as the puzzle draws it, e.g. `- a (dir)` and
+    /// `- i (file, size=584)`.
+    pub fn listing(&self) -> String {
+        fn walk(filesystem: &Filesystem, dir: usize, depth: usize, out: &mut String) {
+            for (name, node) in filesystem.children(dir) {
+                let indent = "  ".repeat(depth);
+                match node {
+                    Node::Dir(child) => {
+                        out.push_str(&format!("{}- {} (dir)\n", indent, name));
+                        walk(filesystem, child, depth + 1, out);
+                    }
+                    Node::File(size) => {
+                        out.push_str(&format!("{}- {} (file, size={})\n", indent, name, size))
+                    }
+                }
+            }
+        }
+        let mut out = String::from("- / (dir)\n");
+        walk(self, ROOT, 1, &mut out);
+        out
+    }
+
+    /// The tree drawn like `tree --du`, each entry prefixed by its size.
+    pub fn tree(&self) -> String {
+        fn walk(
+            filesystem: &Filesystem,
+            sizes: &[usize],
+            dir: usize,
+            prefix: &str,
+            out: &mut String,
+        ) {
+            let children = filesystem.children(dir);
+            let width = sizes[ROOT].to_string().len();
+            for (i, (name, node)) in children.iter().enumerate() {
+                let last = i + 1 == children.len();
+                let branch = if last { "└── " } else { "├── " };
+                let size = match node {
+                    Node::Dir(child) => sizes[*child],
+                    Node::File(size) => *size,
+                };
+                out.push_str(&format!(
+                    "{}{}[{:>w$}]  {}\n",
+                    prefix,
+                    branch,
+                    size,
+                    name,
+                    w = width
+                ));
+                if let Node::Dir(child) = node {
+                    let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                    walk(filesystem, sizes, *child, &prefix, out);
+                }
+            }
+        }
+        let sizes = self.sizes();
+        let mut out = format!("[{}]  /\n", sizes[ROOT]);
+        walk(self, &sizes, ROOT, "", &mut out);
+        let dirs = self.descendants(ROOT);
+        let files: usize = dirs.iter().map(|&d| self.dirs[d].files.len()).sum();
+        out.push_str(&format!(
+            "\n{} directories, {} files\n",
+            dirs.len() - 1,
+            files
+        ));
+        out
+    }
+
+    /// Every directory with its total size like `du -h`, largest first.
+    pub fn du(&self) -> String {
+        self.du_from(ROOT)
+    }
+
+    /// The `du -h` report of `dir` and the directories below it.
+    pub(super) fn du_from(&self, dir: usize) -> String {
+        let sizes = self.sizes();
+        let mut dirs: Vec<(usize, String)> = self
+            .descendants(dir)
+            .into_iter()
+            .map(|dir| (sizes[dir], self.path(dir)))
+            .collect();
+        dirs.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.cmp(&b.1)));
+        dirs.iter()
+            .map(|(size, path)| format!("{}\t{}\n", human(*size), path))
+            .collect()
+    }
+
+    /// The tree as nested JSON objects with total sizes for directories.
+    pub fn to_json(&self) -> String {
+        fn node(filesystem: &Filesystem, sizes: &[usize], name: &str, dir: usize) -> Value {
+            let children: Vec<Value> = filesystem
+                .children(dir)
+                .into_iter()
+                .map(|(name, child)| match child {
+                    Node::Dir(child) => node(filesystem, sizes, name, child),
+                    Node::File(size) => json!({ "name": name, "type": "file", "size": size }),
+                })
+                .collect();
+            json!({ "name": name, "type": "dir", "size": sizes[dir], "children": children })
+        }
+        let value = node(self, &self.sizes(), "/", ROOT);
+        serde_json::to_string_pretty(&value).unwrap()
+    }
+
+    /// The tree as a Graphviz digraph, directories as boxes and files as notes.
+    pub fn to_dot(&self) -> String {
+        let sizes = self.sizes();
+        let label = |name: &str, size: usize| {
+            format!(
+                "{}\\n{}",
+                name.replace('\\', "\\\\").replace('"', "\\\""),
+                size
+            )
+        };
+        let mut out = String::from("digraph filesystem {\n    node [shape=box];\n");
+        let mut files = 0;
+        for dir in self.descendants(ROOT) {
+            let directory = &self.dirs[dir];
+            out.push_str(&format!(
+                "    d{} [label=\"{}\"];\n",
+                dir,
+                label(&directory.name, sizes[dir])
+            ));
+            if let Some(parent) = directory.parent {
+                out.push_str(&format!("    d{} -> d{};\n", parent, dir));
+            }
+            for (name, &size) in &directory.files {
+                out.push_str(&format!(
+                    "    f{} [label=\"{}\", shape=note];\n    d{} -> f{};\n",
+                    files,
+                    label(name, size),
+                    dir,
+                    files
+                ));
+                files += 1;
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// A size with a binary unit, rounded up like `du -h` does: `584`, `4.0K`,
+/// `47M`.
+fn human(size: usize) -> String {
+    const UNITS: [&str; 5] = ["K", "M", "G", "T", "P"];
+    if size < 1024 {
+        return size.to_string();
+    }
+    let mut value = size as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit + 1 < UNITS.len() {
+        value /= 1024.0;
+        unit += 1;
+    }
+    let tenths = (value * 10.0).ceil();
+    if tenths < 100.0 {
+        format!("{:.1}{}", tenths / 10.0, UNITS[unit])
+    } else {
+        format!("{}{}", value.ceil(), UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::day07::tests::EXAMPLE;
+
+    fn example() -> Filesystem {
+        EXAMPLE.parse().unwrap()
+    }
+
+    #[test]
+    fn test_listing() {
+        assert_eq!(
+            example().listing(),
+            "\
+- / (dir)
+  - a (dir)
+    - e (dir)
+      - i (file, size=584)
+    - f (file, size=29116)
+    - g (file, size=2557)
+    - h.lst (file, size=62596)
+  - b.txt (file, size=14848514)
+  - c.dat (file, size=8504156)
+  - d (dir)
+    - d.ext (file, size=5626152)
+    - d.log (file, size=8033020)
+    - j (file, size=4060174)
+    - k (file, size=7214296)
+"
+        );
+    }
+
+    #[test]
+    fn test_tree() {
+        let tree = example().tree();
+        let lines: Vec<&str> = tree.lines().collect();
+        assert_eq!(lines[0], "[48381165]  /");
+        assert_eq!(lines[1], "├── [   94853]  a");
+        assert_eq!(lines[2], "│   ├── [     584]  e");
+        assert_eq!(lines[3], "│   │   └── [     584]  i");
+        assert_eq!(lines[10], "    ├── [ 5626152]  d.ext");
+        assert!(tree.ends_with("\n3 directories, 10 files\n"));
+    }
+
+    #[test]
+    fn test_du() {
+        assert_eq!(example().du(), "47M\t/\n24M\t/d\n93K\t/a\n584\t/a/e\n");
+        assert_eq!(human(4096), "4.0K");
+        assert_eq!(human(4097), "4.1K");
+        assert_eq!(human(10 * 1024 - 1), "10K");
+    }
+
+    #[test]
+    fn test_to_json() {
+        let value: Value = serde_json::from_str(&example().to_json()).unwrap();
+        assert_eq!(value["size"], 48381165);
+        assert_eq!(value["children"][0]["name"], "a");
+        assert_eq!(
+            value["children"][0]["children"][0]["children"][0],
+            json!({ "name": "i", "type": "file", "size": 584 })
+        );
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let dot = example().to_dot();
+        assert!(dot.starts_with("digraph filesystem {\n"));
+        assert!(dot.contains("    d0 [label=\"/\\n48381165\"];\n"));
+        assert!(dot.contains("    d0 -> d1;\n"));
+        assert!(dot.contains("[label=\"i\\n584\", shape=note];\n"));
+        assert_eq!(dot.matches(" -> ").count(), 3 + 10);
+    }
+}
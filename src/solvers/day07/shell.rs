@@ -0,0 +1,218 @@
+//! A small interactive shell over the reconstructed filesystem, to try out
+//! which directory to delete.
+
+use std::cmp::Ordering;
+
+use super::{Filesystem, Node, ROOT};
+
+pub const HELP: &str = "\
+cd PATH              change directory
+ls [PATH]            list a directory like the puzzle's ls
+du [PATH]            sizes of a directory and everything below it
+find [PATH] -size N  directories larger (+N), smaller (-N) or exactly N
+rm PATH              delete a directory and show the space it frees
+df                   disk usage against the budget
+pwd                  current directory
+help                 this text";
+
+pub struct Shell {
+    filesystem: Filesystem,
+    cwd: usize,
+    capacity: usize,
+    needed: usize,
+}
+
+impl Shell {
+    /// A shell at the root of `filesystem`, on a disk of `capacity` that
+    /// needs `needed` free.
+    pub fn new(filesystem: Filesystem, capacity: usize, needed: usize) -> Self {
+        Self {
+            filesystem,
+            cwd: ROOT,
+            capacity,
+            needed,
+        }
+    }
+
+    pub fn prompt(&self) -> String {
+        format!("{}$ ", self.filesystem.path(self.cwd))
+    }
+
+    fn resolve(&self, path: Option<&&str>) -> Result<usize, String> {
+        match path {
+            Some(path) => self
+                .filesystem
+                .resolve(self.cwd, path)
+                .map_err(|_| format!("no such directory: {}", path)),
+            None => Ok(self.cwd),
+        }
+    }
+
+    /// Runs one command line and returns what it prints.
+    pub fn execute(&mut self, line: &str) -> Result<String, String> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match words.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return Ok(String::new()),
+        };
+        match (command, args) {
+            ("cd", [_]) => {
+                self.cwd = self.resolve(args.first())?;
+                Ok(String::new())
+            }
+            ("ls", [] | [_]) => Ok(self.ls(self.resolve(args.first())?)),
+            ("du", [] | [_]) => {
+                let dir = self.resolve(args.first())?;
+                Ok(self.filesystem.du_from(dir).trim_end().to_string())
+            }
+            ("find", _) => self.find(args),
+            ("rm", [_]) => self.rm(self.resolve(args.first())?),
+            ("df", []) => Ok(self.df()),
+            ("pwd", []) => Ok(self.filesystem.path(self.cwd)),
+            ("help", []) => Ok(HELP.to_string()),
+            _ => Err(format!("cannot run {:?}, try help", line.trim())),
+        }
+    }
+
+    fn ls(&self, dir: usize) -> String {
+        let lines: Vec<String> = self
+            .filesystem
+            .children(dir)
+            .into_iter()
+            .map(|(name, node)| match node {
+                Node::Dir(_) => format!("dir {}", name),
+                Node::File(size) => format!("{} {}", size, name),
+            })
+            .collect();
+        lines.join("\n")
+    }
+
+    fn find(&self, args: &[&str]) -> Result<String, String> {
+        let (path, size) = match args {
+            [path, "-size", size] => (Some(path), size),
+            ["-size", size] => (None, size),
+            _ => return Err("usage: find [PATH] -size [+|-]N".to_string()),
+        };
+        let (wanted, size) =
+            match size.split_at(size.find(|c: char| c.is_ascii_digit()).unwrap_or(0)) {
+                ("+", size) => (Ordering::Greater, size),
+                ("-", size) => (Ordering::Less, size),
+                ("", size) => (Ordering::Equal, size),
+                _ => return Err(format!("invalid size {:?}", size)),
+            };
+        let size: usize = size
+            .parse()
+            .map_err(|_| format!("invalid size {:?}", size))?;
+        let sizes = self.filesystem.sizes();
+        let mut found: Vec<(usize, String)> = self
+            .filesystem
+            .descendants(self.resolve(path)?)
+            .into_iter()
+            .filter(|&dir| sizes[dir].cmp(&size) == wanted)
+            .map(|dir| (sizes[dir], self.filesystem.path(dir)))
+            .collect();
+        found.sort();
+        let lines: Vec<String> = found
+            .iter()
+            .map(|(size, path)| format!("{}\t{}", size, path))
+            .collect();
+        Ok(lines.join("\n"))
+    }
+
+    /// Detaches `dir` from its parent, moving out of it first if needed.
+    fn rm(&mut self, dir: usize) -> Result<String, String> {
+        let parent = self.filesystem.dirs[dir]
+            .parent
+            .ok_or_else(|| "cannot remove /".to_string())?;
+        let path = self.filesystem.path(dir);
+        let freed = self.filesystem.sizes()[dir];
+        if self.filesystem.descendants(dir).contains(&self.cwd) {
+            self.cwd = parent;
+        }
+        let name = self.filesystem.dirs[dir].name.clone();
+        self.filesystem.dirs[parent].dirs.remove(&name);
+        self.filesystem.dirs[dir].parent = None;
+        Ok(format!(
+            "removed {}, freeing {}\n{}",
+            path,
+            freed,
+            self.df()
+        ))
+    }
+
+    fn df(&self) -> String {
+        let used = self.filesystem.sizes()[ROOT];
+        let free = self.capacity.saturating_sub(used);
+        let verdict = match self.needed.checked_sub(free) {
+            Some(missing) if missing > 0 => format!("{} short", missing),
+            _ => "enough for the update".to_string(),
+        };
+        format!(
+            "used {} of {}, free {}, needed {}: {}",
+            used, self.capacity, free, self.needed, verdict
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solvers::day07::tests::EXAMPLE;
+
+    fn shell() -> Shell {
+        Shell::new(EXAMPLE.parse().unwrap(), 70000000, 30000000)
+    }
+
+    #[test]
+    fn test_navigation() {
+        let mut shell = shell();
+        assert_eq!(
+            shell.execute("ls").unwrap(),
+            "dir a\n14848514 b.txt\n8504156 c.dat\ndir d"
+        );
+        shell.execute("cd a/e").unwrap();
+        assert_eq!(shell.prompt(), "/a/e$ ");
+        assert_eq!(shell.execute("ls").unwrap(), "584 i");
+        assert_eq!(shell.execute("du ..").unwrap(), "93K\t/a\n584\t/a/e");
+        assert!(shell.execute("cd x").is_err());
+        assert!(shell.execute("cd").is_err());
+        assert!(shell.execute("mkdir x").is_err());
+        assert_eq!(shell.execute("").unwrap(), "");
+    }
+
+    #[test]
+    fn test_find() {
+        let mut shell = shell();
+        assert_eq!(
+            shell.execute("find -size +8381165").unwrap(),
+            "24933642\t/d\n48381165\t/"
+        );
+        assert_eq!(shell.execute("find a -size -1000").unwrap(), "584\t/a/e");
+        assert_eq!(shell.execute("find -size 584").unwrap(), "584\t/a/e");
+        assert!(shell.execute("find -size big").is_err());
+        assert!(shell.execute("find -size *5").is_err());
+    }
+
+    #[test]
+    fn test_rm() {
+        let mut shell = shell();
+        assert_eq!(
+            shell.execute("df").unwrap(),
+            "used 48381165 of 70000000, free 21618835, needed 30000000: 8381165 short"
+        );
+        shell.execute("cd /a/e").unwrap();
+        assert_eq!(
+            shell.execute("rm /a").unwrap(),
+            "removed /a, freeing 94853\n\
+             used 48286312 of 70000000, free 21713688, needed 30000000: 8286312 short"
+        );
+        assert_eq!(shell.execute("pwd").unwrap(), "/");
+        assert!(shell.execute("cd a").is_err());
+        assert!(shell
+            .execute("rm d")
+            .unwrap()
+            .ends_with("needed 30000000: enough for the update"));
+        assert_eq!(shell.execute("du").unwrap(), "23M\t/");
+        assert!(shell.execute("rm /").is_err());
+    }
+}
@@ -1,5 +1,9 @@
+use std::fmt::Display;
 use std::fs;
-use std::io;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::{config::Config, error::AocError, Answer};
 
 pub mod day01;
 pub mod day02;
@@ -8,9 +12,170 @@ pub mod day04;
 pub mod day05;
 pub mod day06;
 pub mod day07;
+mod params;
+
+pub use params::{parse_assignment, Param, Params};
+
+/// The calendar solved so far, whose inputs live directly in the inputs
+/// directory rather than in a subdirectory named after the year.
+pub const DEFAULT_YEAR: u16 = 2022;
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+}
+
+impl FromStr for Part {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(format!("unknown part {:?}, expected 1 or 2", s)),
+        }
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::One => write!(f, "1"),
+            Part::Two => write!(f, "2"),
+        }
+    }
+}
+
+/// A day's solution. The raw input is parsed once into `Input`, which is
+/// then shared by both parts.
+pub trait Solver {
+    type Input;
+
+    fn year(&self) -> u16 {
+        DEFAULT_YEAR
+    }
+
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+
+    fn params(&self) -> &'static [Param] {
+        &[]
+    }
+
+    fn parse(&self, input: &str) -> Result<Self::Input, AocError>;
+    fn part_one(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError>;
+    fn part_two(&self, input: &Self::Input, params: &Params) -> Result<Answer, AocError>;
+}
+
+/// Object-safe view of a [`Solver`], so that days with different `Input`
+/// types can live in one registry.
+pub trait Puzzle {
+    fn year(&self) -> u16;
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    fn params(&self) -> &'static [Param];
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, AocError>;
+}
+
+/// Input already parsed by its [`Solver`], ready to be solved.
+pub trait Parsed {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, AocError>;
+}
+
+struct Prepared<'a, S: Solver> {
+    solver: &'a S,
+    input: S::Input,
+}
+
+impl<S: Solver> Parsed for Prepared<'_, S> {
+    fn solve(&self, part: Part, params: &Params) -> Result<Answer, AocError> {
+        match part {
+            Part::One => self.solver.part_one(&self.input, params),
+            Part::Two => self.solver.part_two(&self.input, params),
+        }
+    }
+}
+
+impl<S: Solver> Puzzle for S {
+    fn year(&self) -> u16 {
+        Solver::year(self)
+    }
+
+    fn day(&self) -> u8 {
+        Solver::day(self)
+    }
+
+    fn title(&self) -> &'static str {
+        Solver::title(self)
+    }
+
+    fn params(&self) -> &'static [Param] {
+        Solver::params(self)
+    }
+
+    fn parse<'a>(&'a self, input: &str) -> Result<Box<dyn Parsed + 'a>, AocError> {
+        Ok(Box::new(Prepared {
+            solver: self,
+            input: Solver::parse(self, input)?,
+        }))
+    }
+}
+
+pub fn timed<T>(f: impl FnOnce() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub const SOLVERS: &[&dyn Puzzle] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+];
+
+/// The year's solved days, in order.
+pub fn calendar(year: u16) -> impl Iterator<Item = &'static dyn Puzzle> {
+    SOLVERS.iter().copied().filter(move |s| s.year() == year)
+}
+
+pub fn find(year: u16, day: u8) -> Result<&'static dyn Puzzle, AocError> {
+    calendar(year)
+        .find(|s| s.day() == day)
+        .ok_or(AocError::UnknownDay(year, day))
+}
+
+pub fn read_lines(filename: &str) -> Result<String, AocError> {
+    let path = Config::load()?.inputs_dir()?.join(filename);
+    fs::read_to_string(&path).map_err(|e| AocError::Read(path.display().to_string(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered_by_day() {
+        let days: Vec<u8> = calendar(DEFAULT_YEAR).map(|s| s.day()).collect();
+        assert_eq!(days, (1..=days.len() as u8).collect::<Vec<u8>>());
+        for year in SOLVERS.iter().map(|s| s.year()) {
+            let days: Vec<u8> = calendar(year).map(|s| s.day()).collect();
+            assert!(days.windows(2).all(|w| w[0] < w[1]));
+        }
+    }
 
-pub fn read_lines(filename: &str) -> io::Result<String> {
-    let cwd = std::env::current_dir()?;
-    let path = cwd.join("inputs").join(filename);
-    fs::read_to_string(path)
+    #[test]
+    fn test_find() {
+        assert_eq!(find(2022, 5).map(|s| s.title()).ok(), Some("Supply Stacks"));
+        assert!(matches!(find(2022, 0), Err(AocError::UnknownDay(2022, 0))));
+        assert!(matches!(find(2015, 5), Err(AocError::UnknownDay(2015, 5))));
+    }
 }
@@ -0,0 +1,103 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use crate::error::AocError;
+
+/// A tunable constant of a solver, settable from the command line with
+/// `--param name=value`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Param {
+    pub name: &'static str,
+    pub default: &'static str,
+    pub description: &'static str,
+}
+
+/// Values of a solver's declared parameters, defaults filled in.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    /// Applies `overrides` on top of the `declared` defaults, rejecting
+    /// parameters the solver doesn't declare.
+    pub fn new<I>(declared: &[Param], overrides: I) -> Result<Params, AocError>
+    where
+        I: IntoIterator<Item = (String, String)>,
+    {
+        let mut values: BTreeMap<String, String> = declared
+            .iter()
+            .map(|p| (p.name.to_string(), p.default.to_string()))
+            .collect();
+        for (name, value) in overrides {
+            match values.get_mut(&name) {
+                Some(v) => *v = value,
+                None => {
+                    let known: Vec<&str> = declared.iter().map(|p| p.name).collect();
+                    return Err(AocError::Param(format!(
+                        "unknown parameter {:?}, expected one of: {}",
+                        name,
+                        if known.is_empty() {
+                            "none".to_string()
+                        } else {
+                            known.join(", ")
+                        }
+                    )));
+                }
+            }
+        }
+        Ok(Params(values))
+    }
+
+    pub fn defaults(declared: &[Param]) -> Params {
+        Self::new(declared, []).unwrap()
+    }
+
+    pub fn get<T>(&self, name: &str) -> Result<T, AocError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = self
+            .0
+            .get(name)
+            .ok_or_else(|| AocError::Param(format!("parameter {:?} is not declared", name)))?;
+        value
+            .parse()
+            .map_err(|e| AocError::Param(format!("{}={}: {}", name, value, e)))
+    }
+}
+
+/// Parses a `name=value` command line argument.
+pub fn parse_assignment(s: &str) -> Result<(String, String), AocError> {
+    s.split_once('=')
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .ok_or_else(|| AocError::Param(format!("expected name=value, got {:?}", s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DECLARED: &[Param] = &[Param {
+        name: "top",
+        default: "3",
+        description: "",
+    }];
+
+    #[test]
+    fn test_defaults_and_overrides() {
+        assert_eq!(Params::defaults(DECLARED).get::<usize>("top").unwrap(), 3);
+        let params = Params::new(DECLARED, [parse_assignment("top=1").unwrap()]).unwrap();
+        assert_eq!(params.get::<usize>("top").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_errors() {
+        let unknown = Params::new(DECLARED, [("bottom".to_string(), "1".to_string())]);
+        assert!(matches!(unknown, Err(AocError::Param(_))));
+        let params = Params::new(DECLARED, [("top".to_string(), "x".to_string())]).unwrap();
+        assert!(matches!(
+            params.get::<usize>("top"),
+            Err(AocError::Param(_))
+        ));
+        assert!(matches!(parse_assignment("top"), Err(AocError::Param(_))));
+    }
+}
@@ -0,0 +1,326 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use regex::Regex;
+
+use aoc22::{config::Config, error::AocError, solvers::Part};
+
+use crate::answers::{key, table_name};
+
+pub const GUESSES_FILE: &str = "guesses.toml";
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Outcome {
+    const ALL: [Outcome; 4] = [
+        Outcome::Correct,
+        Outcome::TooHigh,
+        Outcome::TooLow,
+        Outcome::Wrong,
+    ];
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Wrong => write!(f, "wrong"),
+        }
+    }
+}
+
+/// What the server made of a submitted answer.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Response {
+    Outcome(Outcome),
+    RateLimited(Duration),
+    /// The part was already solved, or part one isn't yet.
+    WrongLevel,
+}
+
+pub fn parse_response(html: &str) -> Result<Response, AocError> {
+    if html.contains("That's the right answer") {
+        Ok(Response::Outcome(Outcome::Correct))
+    } else if html.contains("That's not the right answer") {
+        Ok(Response::Outcome(if html.contains("too high") {
+            Outcome::TooHigh
+        } else if html.contains("too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Wrong
+        }))
+    } else if html.contains("You gave an answer too recently") {
+        let wait = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+        let seconds = wait.captures(html).map_or(0, |c| {
+            let number = |i| c.get(i).map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+            number(1) * 60 + number(2)
+        });
+        Ok(Response::RateLimited(Duration::from_secs(seconds)))
+    } else if html.contains("You don't seem to be solving the right level") {
+        Ok(Response::WrongLevel)
+    } else {
+        Err(AocError::Http(
+            "cannot make sense of the answer page".to_string(),
+        ))
+    }
+}
+
+/// What is already known about an answer before submitting it.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    New,
+    Known(Outcome),
+    /// The part was already solved with the given answer.
+    Solved(String),
+    /// At least the given answer, which was too high.
+    AboveTooHigh(String),
+    /// At most the given answer, which was too low.
+    BelowTooLow(String),
+}
+
+/// Every answer submitted so far and how it was judged, stored in the year's
+/// inputs directory as
+///
+/// ```toml
+/// [day01.part1]
+/// 67000 = "too low"
+/// 71502 = "correct"
+/// ```
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Guesses(BTreeMap<(u8, Part), BTreeMap<String, Outcome>>);
+
+impl Guesses {
+    pub fn path(year: u16) -> Result<PathBuf, AocError> {
+        Ok(Config::load()?.year_dir(year)?.join(GUESSES_FILE))
+    }
+
+    pub fn parse(s: &str) -> Result<Guesses, AocError> {
+        let error = |message: String| AocError::Parse(format!("{}: {}", GUESSES_FILE, message));
+        let table: toml::value::Table = toml::from_str(s).map_err(|e| error(e.to_string()))?;
+        let mut guesses = Guesses::default();
+        for (name, parts) in table {
+            let day = name
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(|| error(format!("[{}] is not a day", name)))?;
+            let parts = parts
+                .as_table()
+                .ok_or_else(|| error(format!("[{}] is not a table", name)))?;
+            for (k, answers) in parts {
+                let part = Part::ALL
+                    .into_iter()
+                    .find(|p| key(*p) == *k)
+                    .ok_or_else(|| error(format!("unknown part {} in [{}]", k, name)))?;
+                let answers = answers
+                    .as_table()
+                    .ok_or_else(|| error(format!("[{}.{}] is not a table", name, k)))?;
+                for (answer, outcome) in answers {
+                    let outcome = Outcome::ALL
+                        .into_iter()
+                        .find(|o| Some(o.to_string().as_str()) == outcome.as_str())
+                        .ok_or_else(|| {
+                            error(format!("unknown outcome {} for {:?}", outcome, answer))
+                        })?;
+                    guesses.insert(day, part, answer.clone(), outcome);
+                }
+            }
+        }
+        Ok(guesses)
+    }
+
+    /// Loads the guesses file, treating a missing file as no guesses.
+    pub fn load(year: u16) -> Result<Guesses, AocError> {
+        let path = Self::path(year)?;
+        match fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Guesses::default()),
+            Err(e) => Err(AocError::Read(path.display().to_string(), e)),
+        }
+    }
+
+    pub fn save(&self, year: u16) -> Result<(), AocError> {
+        self.write(&Self::path(year)?)
+    }
+
+    /// Writes the guesses to `path`, creating its directory first.
+    fn write(&self, path: &Path) -> Result<(), AocError> {
+        let error = |e| AocError::Write(path.display().to_string(), e);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(error)?;
+        }
+        fs::write(path, self.to_toml()).map_err(error)
+    }
+
+    pub fn to_toml(&self) -> String {
+        let mut table = toml::value::Table::new();
+        for ((day, part), answers) in &self.0 {
+            let answers = answers
+                .iter()
+                .map(|(answer, outcome)| (answer.clone(), toml::Value::String(outcome.to_string())))
+                .collect();
+            table
+                .entry(table_name(*day))
+                .or_insert_with(|| toml::Value::Table(toml::value::Table::new()))
+                .as_table_mut()
+                .unwrap()
+                .insert(key(*part), toml::Value::Table(answers));
+        }
+        toml::to_string(&table).unwrap()
+    }
+
+    pub fn insert(&mut self, day: u8, part: Part, answer: String, outcome: Outcome) {
+        self.0
+            .entry((day, part))
+            .or_default()
+            .insert(answer, outcome);
+    }
+
+    pub fn check(&self, day: u8, part: Part, answer: &str) -> Check {
+        let answers = match self.0.get(&(day, part)) {
+            Some(answers) => answers,
+            None => return Check::New,
+        };
+        if let Some(outcome) = answers.get(answer) {
+            return Check::Known(*outcome);
+        }
+        if let Some((correct, _)) = answers.iter().find(|(_, o)| **o == Outcome::Correct) {
+            return Check::Solved(correct.clone());
+        }
+        let value = match answer.parse::<i128>() {
+            Ok(value) => value,
+            Err(_) => return Check::New,
+        };
+        let bound = |outcome: Outcome| {
+            answers
+                .iter()
+                .filter(move |(_, o)| **o == outcome)
+                .filter_map(|(a, _)| Some((a.parse::<i128>().ok()?, a)))
+        };
+        if let Some((_, high)) = bound(Outcome::TooHigh).filter(|(h, _)| value >= *h).min() {
+            return Check::AboveTooHigh(high.clone());
+        }
+        if let Some((_, low)) = bound(Outcome::TooLow).filter(|(l, _)| value <= *l).max() {
+            return Check::BelowTooLow(low.clone());
+        }
+        Check::New
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{mock, Client};
+
+    const RIGHT: &str = "<article><p>That's the right answer! You are one gold star closer to \
+        collecting enough star fruit.</p></article>";
+    const TOO_HIGH: &str = "<article><p>That's not the right answer; your answer is too high. \
+        Please wait one minute before trying again.</p></article>";
+    const TOO_RECENT: &str = "<article><p>You gave an answer too recently; you have to wait \
+        after submitting an answer before trying again.  You have 4m 32s left to wait.</p></article>";
+    const WRONG_LEVEL: &str = "<article><p>You don't seem to be solving the right level.  Did \
+        you already complete it?</p></article>";
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(RIGHT).unwrap(),
+            Response::Outcome(Outcome::Correct)
+        );
+        assert_eq!(
+            parse_response(TOO_HIGH).unwrap(),
+            Response::Outcome(Outcome::TooHigh)
+        );
+        assert_eq!(
+            parse_response(TOO_RECENT).unwrap(),
+            Response::RateLimited(Duration::from_secs(272))
+        );
+        assert_eq!(parse_response(WRONG_LEVEL).unwrap(), Response::WrongLevel);
+        assert!(parse_response("<html></html>").is_err());
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let mut guesses = Guesses::default();
+        guesses.insert(1, Part::One, "67000".to_string(), Outcome::TooLow);
+        guesses.insert(1, Part::One, "71502".to_string(), Outcome::Correct);
+        guesses.insert(5, Part::Two, "CMZ".to_string(), Outcome::Wrong);
+        assert_eq!(Guesses::parse(&guesses.to_toml()).unwrap(), guesses);
+        assert!(Guesses::parse("[day01.part1]\n1 = \"close\"").is_err());
+    }
+
+    #[test]
+    fn test_write_creates_year_dir() {
+        let inputs = std::env::temp_dir().join(format!("aoc22-guesses-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&inputs);
+        let config = Config::parse(&format!("inputs = {:?}", inputs), &inputs).unwrap();
+        let path = config.year_dir(2023).unwrap().join(GUESSES_FILE);
+        let mut guesses = Guesses::default();
+        guesses.insert(1, Part::One, "500".to_string(), Outcome::TooLow);
+        guesses.write(&path).unwrap();
+        assert_eq!(
+            Guesses::parse(&fs::read_to_string(&path).unwrap()).unwrap(),
+            guesses
+        );
+        fs::remove_dir_all(inputs).unwrap();
+    }
+
+    #[test]
+    fn test_check() {
+        let mut guesses = Guesses::default();
+        guesses.insert(1, Part::One, "100".to_string(), Outcome::TooHigh);
+        guesses.insert(1, Part::One, "200".to_string(), Outcome::TooHigh);
+        guesses.insert(1, Part::One, "10".to_string(), Outcome::TooLow);
+        guesses.insert(1, Part::One, "50".to_string(), Outcome::Wrong);
+
+        assert_eq!(guesses.check(1, Part::Two, "50"), Check::New);
+        assert_eq!(
+            guesses.check(1, Part::One, "50"),
+            Check::Known(Outcome::Wrong)
+        );
+        assert_eq!(guesses.check(1, Part::One, "60"), Check::New);
+        assert_eq!(
+            guesses.check(1, Part::One, "150"),
+            Check::AboveTooHigh("100".to_string())
+        );
+        assert_eq!(
+            guesses.check(1, Part::One, "-3"),
+            Check::BelowTooLow("10".to_string())
+        );
+        assert_eq!(guesses.check(1, Part::One, "ABC"), Check::New);
+
+        guesses.insert(1, Part::One, "60".to_string(), Outcome::Correct);
+        assert_eq!(
+            guesses.check(1, Part::One, "61"),
+            Check::Solved("60".to_string())
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let (url, server) = mock::serve(vec![(200, TOO_HIGH)]);
+        let html = Client::new(&url, "cafe")
+            .submit(2022, 2, Part::Two, "1 2")
+            .unwrap();
+        assert_eq!(
+            parse_response(&html).unwrap(),
+            Response::Outcome(Outcome::TooHigh)
+        );
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("POST /2022/day/2/answer HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=cafe\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=2&answer=1+2"));
+    }
+}
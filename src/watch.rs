@@ -0,0 +1,270 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use serde_json::Value;
+
+use aoc22::{config::Config, error::AocError, runner::input_file, solvers::DEFAULT_YEAR};
+
+use crate::scaffold::module;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Adds the Rust sources below `dir`, if it exists, to `files`.
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), AocError> {
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries {
+            let path = entry?.path();
+            if path.is_dir() {
+                rust_files(&path, files)?;
+            } else if path.extension().is_some_and(|e| e == "rs") {
+                files.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The day's solver source with its submodules, input and example fixtures.
+fn watched_files(root: &Path, inputs: &Path, year: u16, day: u8) -> Result<Vec<PathBuf>, AocError> {
+    let solvers = root.join("src/solvers");
+    let mut files = vec![
+        solvers.join(format!("{}.rs", module(year, day))),
+        inputs.join(input_file(day)),
+    ];
+    rust_files(&solvers.join(module(year, day)), &mut files)?;
+    let prefix = format!("example{:02}", day);
+    if let Ok(entries) = fs::read_dir(inputs) {
+        for entry in entries {
+            let path = entry?.path();
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+            if name.starts_with(&prefix) && name.ends_with(".toml") {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+fn snapshot(files: &[PathBuf]) -> Snapshot {
+    files
+        .iter()
+        .map(|f| (f.clone(), fs::metadata(f).and_then(|m| m.modified()).ok()))
+        .collect()
+}
+
+fn changed(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    after
+        .iter()
+        .filter(|(file, time)| before.get(*file) != Some(time))
+        .chain(before.iter().filter(|(file, _)| !after.contains_key(*file)))
+        .map(|(file, _)| file.clone())
+        .collect()
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Outcome {
+    answer: String,
+    elapsed: Option<Duration>,
+}
+
+/// Picks the answers and timings out of the `--format json` output of a
+/// day's run.
+fn parse_run(stdout: &str) -> Result<Vec<Outcome>, AocError> {
+    let records: Vec<Value> = serde_json::from_str(stdout)
+        .map_err(|e| AocError::Parse(format!("unexpected output of the run: {}", e)))?;
+    Ok(records
+        .iter()
+        .filter(|r| !r["part"].is_null())
+        .map(|r| Outcome {
+            answer: match (r["answer"].as_str(), r["error"].as_str()) {
+                (Some(answer), _) => answer.to_string(),
+                (None, error) => format!("FAILED: {}", error.unwrap_or("?")),
+            },
+            elapsed: r["solve_time_ns"].as_u64().map(Duration::from_nanos),
+        })
+        .collect())
+}
+
+fn describe(previous: Option<&Outcome>, current: &Outcome) -> String {
+    let elapsed = current
+        .elapsed
+        .map_or_else(|| "?".to_string(), |e| format!("{:?}", e));
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return format!("{} in {}", current.answer, elapsed),
+    };
+    let answer = if previous.answer == current.answer {
+        current.answer.clone()
+    } else {
+        format!("{} (was {})", current.answer, previous.answer)
+    };
+    match (previous.elapsed, current.elapsed) {
+        (Some(before), Some(now)) if !before.is_zero() => {
+            let change = (now.as_secs_f64() / before.as_secs_f64() - 1.0) * 100.0;
+            format!("{} in {} ({:+.1}%)", answer, elapsed, change)
+        }
+        _ => format!("{} in {}", answer, elapsed),
+    }
+}
+
+fn cargo(root: &Path, args: &[&str]) -> Result<(bool, String), AocError> {
+    let cargo = env::var_os("CARGO").unwrap_or_else(|| "cargo".into());
+    let output = Command::new(cargo)
+        .args(args)
+        .current_dir(root)
+        .output()
+        .map_err(|e| AocError::Read("cargo".to_string(), e))?;
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    if !output.status.success() {
+        text.push_str(&String::from_utf8_lossy(&output.stderr));
+    }
+    Ok((output.status.success(), text))
+}
+
+/// Runs the day's tests and then the day itself, printing what changed
+/// since `previous`.
+fn rerun(root: &Path, year: u16, day: u8, previous: &[Outcome]) -> Result<Vec<Outcome>, AocError> {
+    let module = module(year, day);
+    let example = match year {
+        DEFAULT_YEAR => format!("example{:02}", day),
+        _ => format!("y{}_example{:02}", year, day),
+    };
+    let (passed, output) = cargo(root, &["test", "--quiet", "--", &module, &example])?;
+    if !passed {
+        println!("{}", output);
+        println!("tests failed");
+        return Ok(Vec::new());
+    }
+    println!("tests passed");
+
+    let year = year.to_string();
+    let day = day.to_string();
+    let args = [
+        "run", "--quiet", "--", "--year", &year, "--format", "json", &day,
+    ];
+    let (solved, output) = cargo(root, &args)?;
+    if !solved {
+        println!("{}", output);
+        return Ok(Vec::new());
+    }
+    let outcomes = parse_run(&output)?;
+    for (i, outcome) in outcomes.iter().enumerate() {
+        println!("part {}: {}", i + 1, describe(previous.get(i), outcome));
+    }
+    Ok(outcomes)
+}
+
+/// Reruns the day whenever its source, input or examples change. Never
+/// returns unless something goes wrong.
+pub fn watch(year: u16, day: u8) -> Result<(), AocError> {
+    let config = Config::load()?;
+    let root = config.root()?;
+    let inputs = config.year_dir(year)?;
+    let mut files = watched_files(&root, &inputs, year, day)?;
+    let mut before = snapshot(&files);
+    println!("watching {} files for day {}", files.len(), day);
+    let mut outcomes = rerun(&root, year, day, &[])?;
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        files = watched_files(&root, &inputs, year, day)?;
+        let after = snapshot(&files);
+        let changes = changed(&before, &after);
+        if changes.is_empty() {
+            continue;
+        }
+        for file in &changes {
+            println!("\nchanged {}", file.display());
+        }
+        before = after;
+        let current = rerun(&root, year, day, &outcomes)?;
+        if !current.is_empty() {
+            outcomes = current;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_run() {
+        let stdout = r#"[
+  {"year": 2022, "day": 1, "part": 1, "answer": "24000", "error": null,
+   "parse_time_ns": 1000, "solve_time_ns": 2000, "status": "ok"},
+  {"year": 2022, "day": 1, "part": 2, "answer": null, "error": "cannot solve: no",
+   "parse_time_ns": 1000, "solve_time_ns": 4000000, "status": "error"}
+]"#;
+        assert_eq!(
+            parse_run(stdout).unwrap(),
+            vec![
+                Outcome {
+                    answer: "24000".to_string(),
+                    elapsed: Some(Duration::from_micros(2)),
+                },
+                Outcome {
+                    answer: "FAILED: cannot solve: no".to_string(),
+                    elapsed: Some(Duration::from_millis(4)),
+                },
+            ]
+        );
+        assert!(parse_run("Resunt is: 24000").is_err());
+    }
+
+    #[test]
+    fn test_describe() {
+        let outcome = |answer: &str, micros| Outcome {
+            answer: answer.to_string(),
+            elapsed: Some(Duration::from_micros(micros)),
+        };
+        assert_eq!(describe(None, &outcome("7", 10)), "7 in 10µs");
+        assert_eq!(
+            describe(Some(&outcome("7", 10)), &outcome("7", 15)),
+            "7 in 15µs (+50.0%)"
+        );
+        assert_eq!(
+            describe(Some(&outcome("5", 10)), &outcome("7", 5)),
+            "7 (was 5) in 5µs (-50.0%)"
+        );
+    }
+
+    #[test]
+    fn test_watched_files() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let inputs = root.join("inputs");
+        let files = watched_files(root, &inputs, DEFAULT_YEAR, 7).unwrap();
+        for file in [
+            "src/solvers/day07.rs",
+            "src/solvers/day07/generate.rs",
+            "src/solvers/day07/render.rs",
+            "src/solvers/day07/shell.rs",
+            "inputs/input07.in",
+            "inputs/example07.toml",
+        ] {
+            assert!(files.contains(&root.join(file)), "{} is not watched", file);
+        }
+        assert!(!files.contains(&root.join("src/solvers/day06.rs")));
+    }
+
+    #[test]
+    fn test_changed() {
+        let a = PathBuf::from("a");
+        let b = PathBuf::from("b");
+        let now = SystemTime::now();
+        let before: Snapshot = [(a.clone(), Some(now)), (b.clone(), None)].into();
+        assert!(changed(&before, &before).is_empty());
+        let after: Snapshot = [(a.clone(), Some(now)), (b.clone(), Some(now))].into();
+        assert_eq!(changed(&before, &after), vec![b]);
+        let after: Snapshot = [(PathBuf::from("c"), None)].into();
+        assert_eq!(changed(&before, &after).len(), 3);
+    }
+}